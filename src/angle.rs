@@ -1,12 +1,18 @@
+use crate::fixed::Q16_16;
 use crate::interpolation::Interpolation;
 use crate::scalar::Scalar;
+use crate::vector::Vector;
 use mlua::FromLua;
-use rlua::{AnyUserData, Lua, MetaMethod, Number, UserData, UserDataMethods};
+use rlua::{Lua, MetaMethod, Number, UserData, UserDataMethods, Value};
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::str::FromStr;
 
 pub type AngleF = Angle<f32>;
 pub type AngleD = Angle<f64>;
+///Deterministic fixed-point angle, see `Q16_16`.
+pub type AngleX = Angle<Q16_16>;
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum AngleOperatorValue<S: Scalar> {
@@ -14,7 +20,7 @@ pub enum AngleOperatorValue<S: Scalar> {
     Scalar(S),
 }
 
-#[derive(Debug, Clone, Copy, PartialOrd, FromLua)]
+#[derive(Debug, Clone, Copy, FromLua)]
 pub enum Angle<S: Scalar> {
     Radians(S),
     Degrees(S),
@@ -33,125 +39,181 @@ impl<S: Scalar> Interpolation<S> for Angle<S> {
     }
 }
 
-impl UserData for AngleF {
-    fn add_methods<'a, M: UserDataMethods<'a, Self>>(methods: &mut M) {
-        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
-
-        methods.add_meta_method(MetaMethod::Add, |_, this, b: AnyUserData| {
-            /*if let Ok(i) = b.borrow::<Number>() {
-                let a = *this;
-                return Ok(a + *i as f32);
-            }
+///Converts a Lua operand of an angle metamethod, numbers are taken in the unit of the other operand.
+fn lua_operand<'lua, S: Scalar>(lua: &'lua Lua, value: Value<'lua>) -> rlua::Result<AngleOperatorValue<S>>
+where
+    Angle<S>: FromLua<'lua>,
+{
+    match value {
+        Value::Integer(i) => Ok(AngleOperatorValue::Scalar(S::from_f64(i as f64))),
+        Value::Number(n) => Ok(AngleOperatorValue::Scalar(S::from_f64(n))),
+        other => Ok(AngleOperatorValue::Angle(Angle::from_lua(other, lua)?)),
+    }
+}
 
-            if let Ok(i) = b.borrow::<f64>() {
-                let a = *this;
-                return Ok(a + i.to_f32());
-            }*/
+///Lua passes metamethod operands in source order, so the number may be on either side.
+fn lua_arithmetic<'lua, S: Scalar>(
+    lua: &'lua Lua,
+    (a, b): (Value<'lua>, Value<'lua>),
+    op: impl Fn(Angle<S>, AngleOperatorValue<S>) -> Angle<S>,
+    reversed: impl Fn(S, S) -> S,
+) -> rlua::Result<Angle<S>>
+where
+    Angle<S>: FromLua<'lua>,
+{
+    match (lua_operand(lua, a)?, lua_operand(lua, b)?) {
+        (AngleOperatorValue::Angle(a), b) => Ok(op(a, b)),
+        (AngleOperatorValue::Scalar(a), AngleOperatorValue::Angle(b)) => Ok(b.map(|b| reversed(a, b))),
+        _ => Err(rlua::Error::RuntimeError("expected an angle operand".to_string())),
+    }
+}
 
-            if let Ok(a) = b.borrow::<Self>() {
-                return Ok(*a + *this);
-            }
-            Ok(*this)
-        });
+fn lua_compare<'lua, S: Scalar>(lua: &'lua Lua, (a, b): (Value<'lua>, Value<'lua>)) -> rlua::Result<Option<Ordering>>
+where
+    Angle<S>: FromLua<'lua>,
+{
+    match (lua_operand(lua, a)?, lua_operand(lua, b)?) {
+        (AngleOperatorValue::Angle(a), AngleOperatorValue::Angle(b)) => Ok(a.partial_cmp(&b)),
+        (AngleOperatorValue::Angle(a), AngleOperatorValue::Scalar(b)) => Ok(a.take().partial_cmp(&b)),
+        (AngleOperatorValue::Scalar(a), AngleOperatorValue::Angle(b)) => Ok(a.partial_cmp(&b.take())),
+        _ => Err(rlua::Error::RuntimeError("expected an angle operand".to_string())),
+    }
+}
 
-        methods.add_meta_method(MetaMethod::Sub, |_, this, b: AnyUserData| {
-            /*if let Ok(i) = b.borrow::<f32>() {
-                let a = *this;
-                return Ok(a - *i);
+macro_rules! lua_angle {
+    ($t:ty[$s:ty], $convert:ident) => {
+        impl UserData for $t {
+            fn add_methods<'a, M: UserDataMethods<'a, Self>>(methods: &mut M) {
+                methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+
+                methods.add_meta_function(MetaMethod::Add, |lua, operands: (Value, Value)| {
+                    lua_arithmetic(lua, operands, |a: Self, b| a + b, |a, b| a + b)
+                });
+                methods.add_meta_function(MetaMethod::Sub, |lua, operands: (Value, Value)| {
+                    lua_arithmetic(lua, operands, |a: Self, b| a - b, |a, b| a - b)
+                });
+                methods.add_meta_function(MetaMethod::Mul, |lua, operands: (Value, Value)| {
+                    lua_arithmetic(lua, operands, |a: Self, b| a * b, |a, b| a * b)
+                });
+                methods.add_meta_function(MetaMethod::Div, |lua, operands: (Value, Value)| {
+                    lua_arithmetic(lua, operands, |a: Self, b| a / b, |a, b| a / b)
+                });
+                methods.add_meta_method(MetaMethod::Unm, |_, this, ()| Ok(this.map(|s| -s)));
+                methods.add_meta_method(MetaMethod::Eq, |_, this, b: Self| Ok(*this == b));
+                methods.add_meta_function(MetaMethod::Lt, |lua, operands: (Value, Value)| {
+                    Ok(lua_compare::<$s>(lua, operands)? == Some(Ordering::Less))
+                });
+                methods.add_meta_function(MetaMethod::Le, |lua, operands: (Value, Value)| {
+                    Ok(matches!(
+                        lua_compare::<$s>(lua, operands)?,
+                        Some(Ordering::Less | Ordering::Equal)
+                    ))
+                });
+
+                methods.add_method("sin", |_, this, ()| Ok(this.sin()));
+                methods.add_method("cos", |_, this, ()| Ok(this.cos()));
+                methods.add_method("tan", |_, this, ()| Ok(this.tan()));
+                methods.add_method("normalize", |_, this, ()| Ok(this.normalize_0_to_2pi()));
+                methods.add_method("normalize_0_to_2pi", |_, this, ()| Ok(this.normalize_0_to_2pi()));
+                methods.add_method("normalize_signed", |_, this, ()| Ok(this.normalize_signed()));
+                methods.add_method("shortest_difference", |_, this, b: Self| Ok(this.shortest_difference(b)));
+                methods.add_method("lerp_shortest", |_, this, (b, t): (Self, Number)| Ok(Self::lerp_shortest(*this, b, <$s>::from_f64(t))));
+                methods.add_method("take_in", |_, this, unit: AngleUnit| Ok(this.take_in(unit).to_f64()));
+                methods.add_method("dms", |_, this, ()| Ok(this.to_dms().to_string()));
+                methods.add_method(stringify!($convert), |_, this, ()| Ok(this.$convert()));
+                methods.add_method("to_rad", |_, this, ()| Ok(this.to_radians()));
+                methods.add_method("to_deg", |_, this, ()| Ok(this.to_degrees()));
+                methods.add_method("take", |_, this, ()| Ok(this.take().to_f64()));
             }
+        }
+    };
+}
 
-            if let Ok(i) = b.borrow::<f64>() {
-                let a = *this;
-                return Ok(a - i.to_f32());
-            }*/
+lua_angle!(AngleF[f32], to_angled);
+lua_angle!(AngleD[f64], to_anglef);
+lua_angle!(AngleX[Q16_16], to_angled);
 
-            if let Ok(a) = b.borrow::<Self>() {
-                return Ok(*a - *this);
-            }
-            Ok(*this)
-        });
+impl AngleF {
+    pub fn to_angled(self) -> AngleD {
+        match self {
+            Angle::Radians(r) => AngleD::Radians(r as f64),
+            Angle::Degrees(d) => AngleD::Degrees(d as f64),
+        }
+    }
 
-        methods.add_meta_method(MetaMethod::Mul, |_, this, b: AnyUserData| {
-            /*if let Ok(i) = b.borrow::<f32>() {
-                let a = *this;
-                return Ok(a * *i);
-            }
+    pub fn load_lua(lua: &Lua) -> rlua::Result<()> {
+        lua.globals().set(
+            "radf",
+            lua.create_function(|_, args: Number| Ok(Self::Radians(args as f32)))?,
+        )?;
 
-            if let Ok(i) = b.borrow::<f64>() {
-                let a = *this;
-                return Ok(a * i.to_f32());
-            }*/
+        lua.globals().set(
+            "degf",
+            lua.create_function(|_, args: Number| Ok(Self::Degrees(args as f32)))?,
+        )?;
 
-            if let Ok(a) = b.borrow::<Self>() {
-                return Ok(*a * *this);
-            }
-            Ok(*this)
-        });
+        lua.globals().set(
+            "turnf",
+            lua.create_function(|_, args: Number| Ok(Self::turns(args as f32)))?,
+        )?;
 
-        methods.add_meta_method(MetaMethod::Add, |_, this, b: AnyUserData| {
-            /*if let Ok(i) = b.borrow::<f32>() {
-                let a = *this;
-                return Ok(a / *i);
-            }
+        lua.globals().set(
+            "gradf",
+            lua.create_function(|_, args: Number| Ok(Self::gradians(args as f32)))?,
+        )?;
 
-            if let Ok(i) = b.borrow::<f64>() {
-                let a = *this;
-                return Ok(a / i.to_f32());
-            }*/
+        lua.globals().set("dmsf", lua.create_function(dms_from_lua::<f32>)?)?;
 
-            if let Ok(a) = b.borrow::<Self>() {
-                return Ok(*a / *this);
-            }
-            Ok(*this)
-        });
+        lua.globals().set("asinf", lua.create_function(|_, s: f32| Ok(Self::asin(s)))?)?;
+        lua.globals().set("acosf", lua.create_function(|_, s: f32| Ok(Self::acos(s)))?)?;
+        lua.globals().set(
+            "atanf",
+            lua.create_function(|_, (y, x): (f32, Option<f32>)| Ok(x.map_or(Self::atan(y), |x| Self::atan2(y, x))))?,
+        )?;
+        lua.globals().set("atan2f", lua.create_function(|_, (y, x): (f32, f32)| Ok(Self::atan2(y, x)))?)?;
 
-        methods.add_method("to_rad", |_, this, ()| Ok(this.to_radians()));
-        methods.add_method("to_deg", |_, this, ()| Ok(this.to_degrees()));
-        methods.add_method("take", |_, this, ()| Ok(this.take().to_f64()));
+        Ok(())
     }
-    /*fn add_methods<'lua, M: rlua::UserDataMethods<'lua, Self>>(methods: &mut M) {
-
-    }*/
 }
 
-impl UserData for AngleD {
-    fn add_methods<'a, M: UserDataMethods<'a, Self>>(methods: &mut M) {
-        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
-
-        methods.add_meta_method(MetaMethod::Add, |_, this, b: AnyUserData| {
-            /*if let Ok(i) = b.borrow::<f32>() {
-                let a = *this;
-                return Ok(a + i.to_f64());
-            }
+impl AngleX {
+    pub fn to_angled(self) -> AngleD {
+        match self {
+            Angle::Radians(r) => AngleD::Radians(r.to_f64()),
+            Angle::Degrees(d) => AngleD::Degrees(d.to_f64()),
+        }
+    }
 
-            if let Ok(i) = b.borrow::<f64>() {
-                let a = *this;
-                return Ok(a + *i);
-            }*/
+    pub fn load_lua(lua: &Lua) -> rlua::Result<()> {
+        lua.globals().set(
+            "radx",
+            lua.create_function(|_, args: Q16_16| Ok(Self::Radians(args)))?,
+        )?;
 
-            if let Ok(a) = b.borrow::<Self>() {
-                return Ok(*a + *this);
-            }
-            Ok(*this)
-        });
+        lua.globals().set(
+            "degx",
+            lua.create_function(|_, args: Q16_16| Ok(Self::Degrees(args)))?,
+        )?;
 
-        methods.add_method("to_rad", |_, this, ()| Ok(this.to_radians()));
-        methods.add_method("to_deg", |_, this, ()| Ok(this.to_degrees()));
-        methods.add_method("take", |_, this, ()| Ok(this.take().to_f64()));
-    }
-}
+        lua.globals().set(
+            "turnx",
+            lua.create_function(|_, args: Q16_16| Ok(Self::turns(args)))?,
+        )?;
 
-impl AngleF {
-    pub fn load_lua(lua: &Lua) -> rlua::Result<()> {
         lua.globals().set(
-            "radf",
-            lua.create_function(|_, args: Number| Ok(Self::Radians(args as f32)))?,
+            "gradx",
+            lua.create_function(|_, args: Q16_16| Ok(Self::gradians(args)))?,
         )?;
 
+        lua.globals().set("dmsx", lua.create_function(dms_from_lua::<Q16_16>)?)?;
+
+        lua.globals().set("asinx", lua.create_function(|_, s: Q16_16| Ok(Self::asin(s)))?)?;
+        lua.globals().set("acosx", lua.create_function(|_, s: Q16_16| Ok(Self::acos(s)))?)?;
         lua.globals().set(
-            "degf",
-            lua.create_function(|_, args: Number| Ok(Self::Degrees(args as f32)))?,
+            "atanx",
+            lua.create_function(|_, (y, x): (Q16_16, Option<Q16_16>)| Ok(x.map_or(Self::atan(y), |x| Self::atan2(y, x))))?,
         )?;
+        lua.globals().set("atan2x", lua.create_function(|_, (y, x): (Q16_16, Q16_16)| Ok(Self::atan2(y, x)))?)?;
 
         Ok(())
     }
@@ -169,6 +231,26 @@ impl Angle<Number> {
             lua.create_function(|_, args: Number| Ok(Self::Degrees(args)))?,
         )?;
 
+        lua.globals().set(
+            "turn",
+            lua.create_function(|_, args: Number| Ok(Self::turns(args)))?,
+        )?;
+
+        lua.globals().set(
+            "grad",
+            lua.create_function(|_, args: Number| Ok(Self::gradians(args)))?,
+        )?;
+
+        lua.globals().set("dms", lua.create_function(dms_from_lua::<f64>)?)?;
+
+        lua.globals().set("asin", lua.create_function(|_, s: Number| Ok(Self::asin(s)))?)?;
+        lua.globals().set("acos", lua.create_function(|_, s: Number| Ok(Self::acos(s)))?)?;
+        lua.globals().set(
+            "atan",
+            lua.create_function(|_, (y, x): (Number, Option<Number>)| Ok(x.map_or(Self::atan(y), |x| Self::atan2(y, x))))?,
+        )?;
+        lua.globals().set("atan2", lua.create_function(|_, (y, x): (Number, Number)| Ok(Self::atan2(y, x)))?)?;
+
         Ok(())
     }
 
@@ -184,16 +266,39 @@ impl<S: Scalar> Angle<S> {
     pub const RAD_ZERO: Self = Self::Radians(S::ZERO);
     pub const DEG_ZERO: Self = Self::Degrees(S::ZERO);
 
+    pub fn asin(s: S) -> Self {
+        Self::Radians(s.inverse_sine())
+    }
+
+    pub fn acos(s: S) -> Self {
+        Self::Radians(s.inverse_cosine())
+    }
+
+    pub fn atan(s: S) -> Self {
+        Self::Radians(s.inverse_tangent())
+    }
+
+    ///Four quadrant angle of the point `(x, y)` from the positive x-axis, in `(-π, π]`.
+    pub fn atan2(y: S, x: S) -> Self {
+        Self::Radians(y.inverse_tangent2(x))
+    }
+
+    ///Unsigned angle in `[0, π]` between `a` and `b`. Stays accurate for nearly parallel vectors, unlike `acos` of the dot product.
+    pub fn from_vectors<const L: usize>(a: Vector<L, S>, b: Vector<L, S>) -> Self {
+        let (a, b) = (a * b.magnitude(), b * a.magnitude());
+        Self::Radians(S::TWO * (a - b).magnitude().inverse_tangent2((a + b).magnitude()))
+    }
+
     pub fn sin(self) -> S {
-        self.take().sine()
+        self.take_radians().sine()
     }
 
     pub fn cos(self) -> S {
-        self.take().cosine()
+        self.take_radians().cosine()
     }
 
     pub fn tan(self) -> S {
-        self.take().tangent()
+        self.take_radians().tangent()
     }
 
     pub fn sin_cos(self) -> (S, S) {
@@ -239,6 +344,49 @@ impl<S: Scalar> Angle<S> {
         }
     }
 
+    ///One full turn in the unit of `self`.
+    fn turn(self) -> S {
+        match self {
+            Angle::Radians(_) => S::TWO * S::PI,
+            Angle::Degrees(_) => S::from_f64(360.0),
+        }
+    }
+
+    ///The same direction moved into `[0, 2π)`, keeping the unit.
+    pub fn normalize_0_to_2pi(self) -> Self {
+        let turn = self.turn();
+        self.map(|s| {
+            let s = s % turn;
+            let s = crate::ternary!(s < S::ZERO => s + turn ; s);
+            //A tiny negative remainder rounds up to a whole turn.
+            crate::ternary!(s >= turn => S::ZERO ; s)
+        })
+    }
+
+    ///The same direction moved into `(-π, π]`, keeping the unit.
+    pub fn normalize_signed(self) -> Self {
+        let (turn, half) = (self.turn(), self.turn() / S::TWO);
+        self.normalize_0_to_2pi().map(|s| crate::ternary!(s > half => s - turn ; s))
+    }
+
+    ///Signed angle in `(-π, π]` that turns `self` onto `other` the short way, in the unit of `self`.
+    pub fn shortest_difference(self, other: Self) -> Self {
+        (self - other).map(|s| -s).normalize_signed()
+    }
+
+    ///Interpolates from `a` towards `b` the short way round. The result is not normalized.
+    pub fn lerp_shortest(a: Self, b: Self, t: S) -> Self {
+        a + a.shortest_difference(b).map(|s| s * t)
+    }
+
+    ///Applies `f` to the stored value, keeping the unit.
+    pub fn map<F: Fn(S) -> S>(self, f: F) -> Self {
+        match self {
+            Angle::Radians(r) => Self::Radians(f(r)),
+            Angle::Degrees(d) => Self::Degrees(f(d)),
+        }
+    }
+
     pub fn take_radians(self) -> S {
         self.to_radians().take()
     }
@@ -246,6 +394,94 @@ impl<S: Scalar> Angle<S> {
     pub fn take_degrees(self) -> S {
         self.to_degrees().take()
     }
+
+    ///`value` full revolutions, stored as radians.
+    pub fn turns(value: S) -> Self {
+        Self::Radians(value * S::TWO * S::PI)
+    }
+
+    ///`value` gradians (400 to a turn), stored as degrees.
+    pub fn gradians(value: S) -> Self {
+        Self::Degrees(value * S::from_f64(0.9))
+    }
+
+    ///Sign is negative if any component is; magnitudes are summed. Stored as degrees.
+    pub fn from_dms(degrees: S, minutes: S, seconds: S) -> Self {
+        Dms {
+            negative: degrees < S::ZERO || minutes < S::ZERO || seconds < S::ZERO,
+            degrees: degrees.s_abs(),
+            minutes: minutes.s_abs(),
+            seconds: seconds.s_abs(),
+        }
+        .into()
+    }
+
+    ///Builds an angle from `value` in any unit. Only radians and degrees are stored.
+    pub fn from_unit(value: S, unit: AngleUnit) -> Self {
+        match unit {
+            AngleUnit::Radians => Self::Radians(value),
+            AngleUnit::Degrees => Self::Degrees(value),
+            AngleUnit::Turns => Self::turns(value),
+            AngleUnit::Gradians => Self::gradians(value),
+            AngleUnit::ArcMinutes => Self::Degrees(value / S::from_f64(60.0)),
+            AngleUnit::ArcSeconds => Self::Degrees(value / S::from_f64(3600.0)),
+        }
+    }
+
+    ///The value of `self` expressed in `unit`.
+    pub fn take_in(self, unit: AngleUnit) -> S {
+        match unit {
+            AngleUnit::Radians => self.take_radians(),
+            AngleUnit::Degrees => self.take_degrees(),
+            AngleUnit::Turns => self.take_radians() / (S::TWO * S::PI),
+            AngleUnit::Gradians => self.take_degrees() / S::from_f64(0.9),
+            AngleUnit::ArcMinutes => self.take_degrees() * S::from_f64(60.0),
+            AngleUnit::ArcSeconds => self.take_degrees() * S::from_f64(3600.0),
+        }
+    }
+
+    pub fn take_turns(self) -> S {
+        self.take_in(AngleUnit::Turns)
+    }
+
+    pub fn take_gradians(self) -> S {
+        self.take_in(AngleUnit::Gradians)
+    }
+
+    ///Splits into whole degrees, whole arc minutes and arc seconds, rounded to the microarcsecond.
+    pub fn to_dms(self) -> Dms<S> {
+        let degrees = self.take_degrees().to_f64();
+        let total = (degrees.abs() * 3_600_000_000.0).round() / 1_000_000.0;
+        let whole_degrees = (total / 3600.0).floor();
+        let minutes = ((total - whole_degrees * 3600.0) / 60.0).floor();
+
+        Dms {
+            negative: degrees < 0.0 && total > 0.0,
+            degrees: S::from_f64(whole_degrees),
+            minutes: S::from_f64(minutes),
+            seconds: S::from_f64(total - whole_degrees * 3600.0 - minutes * 60.0),
+        }
+    }
+}
+
+///Lua `dms(12, 30, 15)` or `dms("12°30'15\"")`.
+fn dms_from_lua<S: Scalar>(
+    _: &Lua,
+    (degrees, minutes, seconds): (Value, Option<Number>, Option<Number>),
+) -> rlua::Result<Angle<S>> {
+    let component = |n: Option<Number>| S::from_f64(n.unwrap_or(0.0));
+    match degrees {
+        Value::String(text) => text
+            .to_str()?
+            .parse()
+            .map_err(|e: ParseDmsError| rlua::Error::RuntimeError(e.to_string())),
+        Value::Integer(i) => Ok(Angle::from_dms(S::from_f64(i as f64), component(minutes), component(seconds))),
+        Value::Number(n) => Ok(Angle::from_dms(S::from_f64(n), component(minutes), component(seconds))),
+        other => Err(rlua::Error::RuntimeError(format!(
+            "dms expects a number or string, got {}",
+            other.type_name()
+        ))),
+    }
 }
 
 impl<S: Scalar> Into<AngleOperatorValue<S>> for Angle<S> {
@@ -367,6 +603,16 @@ impl<S: Scalar> PartialEq for Angle<S> {
     }
 }
 
+///Compares in the unit of `self`, like `PartialEq`.
+impl<S: Scalar> PartialOrd for Angle<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self {
+            Angle::Radians(r) => r.partial_cmp(&other.take_radians()),
+            Angle::Degrees(d) => d.partial_cmp(&other.take_degrees()),
+        }
+    }
+}
+
 impl<S: Scalar> Display for Angle<S> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -375,3 +621,123 @@ impl<S: Scalar> Display for Angle<S> {
         }
     }
 }
+
+///Units accepted by `Angle::from_unit` and `Angle::take_in`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AngleUnit {
+    Radians,
+    Degrees,
+    Turns,
+    Gradians,
+    ArcMinutes,
+    ArcSeconds,
+}
+
+impl<'lua> FromLua<'lua> for AngleUnit {
+    fn from_lua(value: mlua::Value<'lua>, lua: &'lua mlua::Lua) -> mlua::Result<Self> {
+        match String::from_lua(value, lua)?.to_lowercase().as_str() {
+            "rad" | "radians" => Ok(Self::Radians),
+            "deg" | "degrees" => Ok(Self::Degrees),
+            "turn" | "turns" => Ok(Self::Turns),
+            "grad" | "gradians" => Ok(Self::Gradians),
+            "arcmin" | "arcminutes" => Ok(Self::ArcMinutes),
+            "arcsec" | "arcseconds" => Ok(Self::ArcSeconds),
+            other => Err(mlua::Error::RuntimeError(format!("unknown angle unit {other}"))),
+        }
+    }
+}
+
+///Degrees-minutes-seconds, formatted as `-12°30'15"`. A precision applies to the seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dms<S: Scalar> {
+    pub negative: bool,
+    pub degrees: S,
+    pub minutes: S,
+    pub seconds: S,
+}
+
+impl<S: Scalar> From<Dms<S>> for Angle<S> {
+    fn from(dms: Dms<S>) -> Self {
+        let degrees = dms.degrees + dms.minutes / S::from_f64(60.0) + dms.seconds / S::from_f64(3600.0);
+        Angle::Degrees(crate::ternary!(dms.negative => -degrees ; degrees))
+    }
+}
+
+impl<S: Scalar> Display for Dms<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = crate::ternary!(self.negative => "-" ; "");
+        match f.precision() {
+            Some(p) => write!(f, "{sign}{}°{}'{:.p$}\"", self.degrees, self.minutes, self.seconds),
+            None => write!(f, "{sign}{}°{}'{}\"", self.degrees, self.minutes, self.seconds),
+        }
+    }
+}
+
+///The input was not of the form `[-]D°M'S"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDmsError {
+    pub input: String,
+}
+
+impl Display for ParseDmsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid degrees-minutes-seconds angle \"{}\"", self.input)
+    }
+}
+
+impl std::error::Error for ParseDmsError {}
+
+///Accepts any of the components in order, e.g. `12°`, `30'15"` or `-12° 30′ 15.5″`. A lone number is degrees.
+impl<S: Scalar> FromStr for Dms<S> {
+    type Err = ParseDmsError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = || ParseDmsError { input: input.to_string() };
+        let trimmed = input.trim();
+        let (negative, mut rest) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+
+        let mut fields = [0.0; 3];
+        let mut last: Option<usize> = None;
+        while !rest.trim_start().is_empty() {
+            rest = rest.trim_start();
+            let end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+            let value: f64 = rest[..end].parse().map_err(|_| error())?;
+            let mut marker = rest[end..].chars();
+            let index = match marker.next() {
+                Some('°') => 0,
+                Some('\'' | '′') => 1,
+                Some('"' | '″') => 2,
+                None if last.is_none() => 0,
+                _ => return Err(error()),
+            };
+            if last.is_some_and(|last| index <= last) {
+                return Err(error());
+            }
+            fields[index] = value;
+            last = Some(index);
+            rest = marker.as_str();
+        }
+
+        match last {
+            None => Err(error()),
+            Some(_) => Ok(Dms {
+                negative,
+                degrees: S::from_f64(fields[0]),
+                minutes: S::from_f64(fields[1]),
+                seconds: S::from_f64(fields[2]),
+            }),
+        }
+    }
+}
+
+///Parses degrees-minutes-seconds notation into `Angle::Degrees`.
+impl<S: Scalar> FromStr for Angle<S> {
+    type Err = ParseDmsError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        input.parse::<Dms<S>>().map(Into::into)
+    }
+}
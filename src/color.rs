@@ -387,7 +387,7 @@ impl<S: Scalar> Interpolation<S> for Color<S> {
 
                 match _b.to_hsv() {
                     Color::HSV { h, s, v } => Color::HSV {
-                        h: Angle::lerp(a_h, h, t),
+                        h: Angle::lerp_shortest(a_h, h, t).normalize_0_to_2pi(),
                         s: ColorComponent::Percentage(Percentage::new(S::lerp(
                             a_s.to_percentage().take(),
                             s.to_percentage().take(),
@@ -411,7 +411,7 @@ impl<S: Scalar> Interpolation<S> for Color<S> {
                 match _b.has_alpha() {
                     true => match _b.to_hsva() {
                         Self::HSVA { h, s, v, a } => Self::HSVA {
-                            h: Angle::lerp(a_h, h, t),
+                            h: Angle::lerp_shortest(a_h, h, t).normalize_0_to_2pi(),
                             s: S::lerp(a_s.to_percentage().take(), s.to_percentage().take(), t)
                                 .to_percentage()
                                 .to_color_component(),
@@ -426,7 +426,7 @@ impl<S: Scalar> Interpolation<S> for Color<S> {
                     },
                     false => match _b.to_hsv() {
                         Color::HSV { h, s, v } => Self::HSVA {
-                            h: Angle::lerp(a_h, h, t),
+                            h: Angle::lerp_shortest(a_h, h, t).normalize_0_to_2pi(),
                             s: S::lerp(a_s.to_percentage().take(), s.to_percentage().take(), t)
                                 .to_percentage()
                                 .to_color_component(),
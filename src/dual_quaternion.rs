@@ -0,0 +1,283 @@
+use crate::matrix::matrix4x4::{DecomposeError, Matrix4x4};
+use crate::quaternion::Quaternion;
+use crate::scalar::Scalar;
+use crate::vector::vec3::Vec3;
+use mlua::FromLua;
+use rlua::{UserData, UserDataMethods};
+use std::ops::{Add, Mul, Neg};
+
+pub type DualQuatF = DualQuaternion<f32>;
+pub type DualQuatD = DualQuaternion<f64>;
+
+///Rigid transform, a rotation followed by a translation, as `real + ε·dual`. Blending these instead of
+///matrices keeps skinned joints from collapsing.
+#[derive(Debug, Clone, Copy, PartialEq, FromLua)]
+pub struct DualQuaternion<S: Scalar> {
+    pub real: Quaternion<S>,
+    pub dual: Quaternion<S>,
+}
+
+impl<S: Scalar> DualQuaternion<S> {
+    pub const IDENTITY: Self = Self {
+        real: Quaternion::IDENTITY,
+        dual: Quaternion { w: S::ZERO, xyz: Vec3::ZERO },
+    };
+
+    ///Rotates by `rotation`, then translates by `translation`.
+    pub fn from_rotation_translation(rotation: Quaternion<S>, translation: Vec3<S>) -> Self {
+        let real = rotation.normalized();
+        Self {
+            real,
+            dual: Quaternion::pure(translation) * real * (S::ONE / S::TWO),
+        }
+    }
+
+    pub fn from_rotation(rotation: Quaternion<S>) -> Self {
+        Self::from_rotation_translation(rotation, Vec3::ZERO)
+    }
+
+    pub fn from_translation(translation: Vec3<S>) -> Self {
+        Self::from_rotation_translation(Quaternion::IDENTITY, translation)
+    }
+
+    ///Rigid part of `m`. Scale is discarded.
+    pub fn from_matrix(m: Matrix4x4<S>) -> Result<Self, DecomposeError> {
+        let (translation, rotation, _) = m.try_decompose()?;
+        Ok(Self::from_rotation_translation(rotation, translation))
+    }
+
+    pub fn to_matrix(self) -> Matrix4x4<S> {
+        Matrix4x4::from_quaternion(self.rotation()).then(Matrix4x4::translation(self.translation()))
+    }
+
+    pub fn rotation(self) -> Quaternion<S> {
+        self.real
+    }
+
+    pub fn translation(self) -> Vec3<S> {
+        (self.dual * self.real.conjugate()).xyz * S::TWO
+    }
+
+    ///Quaternion conjugate of both parts. Equal to [`DualQuaternion::inverse`] for unit dual quaternions.
+    pub fn conjugate(self) -> Self {
+        Self {
+            real: self.real.conjugate(),
+            dual: self.dual.conjugate(),
+        }
+    }
+
+    pub fn inverse(self) -> Self {
+        let real = self.real.inverse();
+        Self {
+            real,
+            dual: -(real * self.dual * real),
+        }
+    }
+
+    ///Scales to a unit real part, the form every other method expects.
+    pub fn normalized(self) -> Self {
+        self * (S::ONE / self.real.magnitude())
+    }
+
+    pub fn transform_point(self, point: Vec3<S>) -> Vec3<S> {
+        self.real * point + self.translation()
+    }
+
+    ///Rotates a direction, ignoring translation.
+    pub fn transform_vector(self, vector: Vec3<S>) -> Vec3<S> {
+        self.real * vector
+    }
+
+    ///Screw motion scaled by `t`: the rotation angle and the distance along the screw axis are both
+    ///multiplied by `t`.
+    pub fn pow(self, t: S) -> Self {
+        let q = self.normalized();
+        let half_angle = q.real.w.s_clamp(S::NEG_ONE, S::ONE).inverse_cosine();
+        let sin = half_angle.sine();
+
+        //No rotation, a pure translation scales linearly.
+        if sin.s_abs() < S::from_f64(1e-6) {
+            return Self::from_translation(q.translation() * t);
+        }
+
+        let axis = q.real.xyz * (S::ONE / sin);
+        let pitch = -S::TWO * q.dual.w / sin;
+        let moment = (q.dual.xyz - axis * (pitch / S::TWO * half_angle.cosine())) * (S::ONE / sin);
+
+        let (half_angle, pitch) = (half_angle * t, pitch * t);
+        let (sin, cos) = (half_angle.sine(), half_angle.cosine());
+
+        Self {
+            real: Quaternion { w: cos, xyz: axis * sin },
+            dual: Quaternion {
+                w: -(pitch / S::TWO) * sin,
+                xyz: moment * sin + axis * (pitch / S::TWO * cos),
+            },
+        }
+    }
+
+    ///Screw linear interpolation along the shorter arc, constant speed in rotation and translation.
+    pub fn sclerp(a: Self, b: Self, t: S) -> Self {
+        let b = crate::ternary!(a.real.dot(b.real) < S::ZERO => -b ; b);
+        a * (a.inverse() * b).pow(t)
+    }
+
+    ///Dual quaternion linear blending of weighted transforms, as used for skinning. Signs are aligned
+    ///with the first transform so every rotation takes the short way.
+    pub fn dlb(transforms: &[(Self, S)]) -> Self {
+        let Some((pivot, _)) = transforms.first() else {
+            return Self::IDENTITY;
+        };
+
+        transforms
+            .iter()
+            .map(|(q, weight)| {
+                let q = crate::ternary!(pivot.real.dot(q.real) < S::ZERO => -*q ; *q);
+                q * *weight
+            })
+            .fold(Self::IDENTITY * S::ZERO, |sum, q| sum + q)
+            .normalized()
+    }
+}
+
+impl<S: Scalar> Mul<Self> for DualQuaternion<S> {
+    type Output = Self;
+
+    ///Composition, `rhs` is applied first.
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            real: self.real * rhs.real,
+            dual: self.real * rhs.dual + self.dual * rhs.real,
+        }
+    }
+}
+
+impl<S: Scalar> Mul<S> for DualQuaternion<S> {
+    type Output = Self;
+
+    fn mul(self, rhs: S) -> Self::Output {
+        Self {
+            real: self.real * rhs,
+            dual: self.dual * rhs,
+        }
+    }
+}
+
+impl<S: Scalar> Add<Self> for DualQuaternion<S> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            real: self.real + rhs.real,
+            dual: self.dual + rhs.dual,
+        }
+    }
+}
+
+impl<S: Scalar> Neg for DualQuaternion<S> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            real: -self.real,
+            dual: -self.dual,
+        }
+    }
+}
+
+impl UserData for DualQuatF {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        crate::dual_quaternion_methods!(methods => f32);
+    }
+}
+
+impl UserData for DualQuatD {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        crate::dual_quaternion_methods!(methods => f64);
+    }
+}
+
+crate::lua_dual_quaternion!(dual_quatf: DualQuatF => f32, QuatF, Vec3F, Matrix4x4F, "dualquatf");
+crate::lua_dual_quaternion!(dual_quatd: DualQuatD => f64, QuatD, Vec3D, Matrix4x4D, "dualquatd");
+
+#[macro_export]
+macro_rules! lua_dual_quaternion {
+    ($module:ident: $t:ident => $f:ty, $quat:ident, $vec3:ident, $mat4:ident, $name:literal) => {
+        pub mod $module {
+            use $crate::dual_quaternion::$t;
+            use $crate::matrix::matrix4x4::$mat4;
+            use $crate::quaternion::$quat;
+            use $crate::vector::vec3::$vec3;
+            use $crate::{LuaAssociatedFunction, LuaObject};
+            use rlua::{Function, Lua};
+
+            impl LuaObject for $t {
+                type Args = (Option<$quat>, Option<$vec3>);
+                const CONSTRUCTOR_NAME: &'static str = $name;
+
+                fn create_constructor(lua: &Lua) -> rlua::Result<Function> {
+                    lua.create_function(|_lua, args: Self::Args| {
+                        Ok(Self::from_rotation_translation(
+                            args.0.unwrap_or($quat::IDENTITY),
+                            args.1.unwrap_or($vec3::ZERO),
+                        ))
+                    })
+                }
+
+                fn associated_functions(_lua: &Lua) -> rlua::Result<Vec<LuaAssociatedFunction>> {
+                    Ok(vec![
+                        LuaAssociatedFunction {
+                            function: _lua.create_function(|_, _args: ()| Ok(Self::IDENTITY))?,
+                            name: concat!($name, "_identity"),
+                        },
+                        LuaAssociatedFunction {
+                            function: _lua.create_function(|_, m: $mat4| {
+                                Self::from_matrix(m).map_err(|e| rlua::Error::RuntimeError(e.to_string()))
+                            })?,
+                            name: concat!($name, "_from_matrix"),
+                        },
+                        LuaAssociatedFunction {
+                            function: _lua.create_function(|_, (a, b, t): (Self, Self, $f)| Ok(Self::sclerp(a, b, t)))?,
+                            name: concat!($name, "_sclerp"),
+                        },
+                        LuaAssociatedFunction {
+                            function: _lua.create_function(|_, (transforms, weights): (Vec<Self>, Vec<$f>)| {
+                                if transforms.len() != weights.len() {
+                                    return Err(rlua::Error::RuntimeError(format!(
+                                        "{} transforms but {} weights", transforms.len(), weights.len()
+                                    )));
+                                }
+                                Ok(Self::dlb(&transforms.into_iter().zip(weights).collect::<Vec<_>>()))
+                            })?,
+                            name: concat!($name, "_dlb"),
+                        },
+                    ])
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! dual_quaternion_methods {
+    (
+        $methods:ident => $f:ty
+    ) => {
+        $methods.add_method("rotation", |_lua, this, ()| Ok(this.rotation()));
+        $methods.add_method("translation", |_lua, this, ()| Ok(this.translation()));
+        $methods.add_method("inverse", |_lua, this, ()| Ok(this.inverse()));
+        $methods.add_method("conjugate", |_lua, this, ()| Ok(this.conjugate()));
+        $methods.add_method("normalized", |_lua, this, ()| Ok(this.normalized()));
+        $methods.add_method("pow", |_lua, this, t: $f| Ok(this.pow(t)));
+        $methods.add_method("transform_point", |_lua, this, p: $crate::vector::vec3::Vec3<$f>| Ok(this.transform_point(p)));
+        $methods.add_method("transform_vector", |_lua, this, v: $crate::vector::vec3::Vec3<$f>| Ok(this.transform_vector(v)));
+        $methods.add_method(format!("to_mat4x4{}", <$f as $crate::scalar::Scalar>::NAME), |_lua, this, ()| Ok(this.to_matrix()));
+
+        $methods.add_meta_method(rlua::MetaMethod::ToString, |_lua, this, ()| {
+            Ok(format!("{:?} + ε{:?}", this.real, this.dual))
+        });
+        $methods.add_meta_method(rlua::MetaMethod::Eq, |_lua, this, b: Self| Ok(*this == b));
+        $methods.add_meta_method(rlua::MetaMethod::Mul, |_lua, this, b: Self| Ok(*this * b));
+    };
+}
+
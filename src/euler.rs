@@ -1,19 +1,42 @@
-use mlua::{AnyUserData, FromLua, Integer, Lua, MetaMethod, Number};
-use crate::angle::{Angle, AngleD, AngleF};
+use mlua::{FromLua, Integer, Lua, MetaMethod};
+use crate::angle::{Angle, AngleD, AngleF, AngleX};
+use crate::fixed::Q16_16;
+use crate::interpolation::Interpolation;
+use crate::matrix::matrix3x3::{Matrix3x3, RotationOrder};
 use crate::quaternion::Quaternion;
 use crate::scalar::Scalar;
+use std::ops::{Add, Mul, Neg, Sub};
 
 pub type EulerF = Euler<f32>;
+pub type EulerD = Euler<f64>;
+pub type EulerX = Euler<Q16_16>;
 
+///Build with [`Euler::new`] and [`Euler::with_order`]. Struct literals written before `order` existed
+///no longer compile and need `order: RotationOrder::default()` added.
 #[derive(Debug, Clone, Copy, PartialEq, FromLua)]
 pub struct Euler<S: Scalar> {
     pub yaw: Angle<S>,
     pub pitch: Angle<S>,
     pub roll: Angle<S>,
+    ///How the three axis rotations compose when this becomes a matrix or quaternion.
+    pub order: RotationOrder,
     pub debug_flag: u8
 }
 
 impl<S: Scalar> Euler<S> {
+    pub const DEBUG_FLAG_NONE: u8 = 0;
+    pub const DEBUG_FLAG_GREEK_A: u8 = 1;
+    pub const DEBUG_FLAG_GREEK_B: u8 = 2;
+
+    pub fn new(yaw: Angle<S>, pitch: Angle<S>, roll: Angle<S>) -> Self {
+        Self { yaw, pitch, roll, order: RotationOrder::default(), debug_flag: 0 }
+    }
+
+    pub fn with_order(mut self, order: RotationOrder) -> Self {
+        self.order = order;
+        self
+    }
+
     ///Returns the 3 components in (roll, pitch, yaw) form.
     pub fn take(self) -> (S, S, S) {
         (self.roll.take(), self.pitch.take(), self.yaw.take())
@@ -28,19 +51,11 @@ impl<S: Scalar> Euler<S> {
     }
 
     pub fn to_radians(self) -> Self {
-        let mut out = self.clone();
-        out.roll = out.roll.to_radians();
-        out.yaw = out.yaw.to_radians();
-        out.pitch = out.pitch.to_radians();
-        out
+        self.map(|a| a.to_radians())
     }
 
     pub fn to_degrees(self) -> Self {
-        let mut out = self.clone();
-        out.roll = out.roll.to_degrees();
-        out.yaw = out.yaw.to_degrees();
-        out.pitch = out.pitch.to_degrees();
-        out
+        self.map(|a| a.to_degrees())
     }
 
     pub fn radify(&mut self) {
@@ -51,108 +66,187 @@ impl<S: Scalar> Euler<S> {
         *self = self.to_degrees();
     }
 
+    ///Every component wrapped into `(-180°, 180°]`, describing the same rotation.
+    pub fn wrapped(self) -> Self {
+        self.map(Angle::normalize_signed)
+    }
+
     pub fn to_quat(self) -> Quaternion<S> {
         Quaternion::from_euler(self)
     }
+
+    pub fn to_matrix(self) -> Matrix3x3<S> {
+        Matrix3x3::rotation(self, self.order)
+    }
+
+    fn map<F: Fn(Angle<S>) -> Angle<S>>(self, f: F) -> Self {
+        Self {
+            yaw: f(self.yaw),
+            pitch: f(self.pitch),
+            roll: f(self.roll),
+            ..self
+        }
+    }
+
+    fn zip<F: Fn(Angle<S>, Angle<S>) -> Angle<S>>(self, rhs: Self, f: F) -> Self {
+        Self {
+            yaw: f(self.yaw, rhs.yaw),
+            pitch: f(self.pitch, rhs.pitch),
+            roll: f(self.roll, rhs.roll),
+            ..self
+        }
+    }
 }
 
-impl EulerF {
-    pub const DEBUG_FLAG_NONE: u8 = 0;
-    pub const DEBUG_FLAG_GREEK_A: u8 = 1;
-    pub const DEBUG_FLAG_GREEK_B: u8 = 2;
+///Component-wise, the result keeps the units and order of `self`.
+impl<S: Scalar> Add<Self> for Euler<S> {
+    type Output = Self;
 
-    pub fn from_lua(lua: &Lua) -> rlua::Result<()> {
-        let t = lua.create_function(|_, (yaw, pitch, roll): (Option<AnyUserData>, Option<AnyUserData>, Option<AnyUserData>)| {
-            let yaw = yaw.map(|e| {
-                if let Ok(e) = e.borrow::<AngleF>() {
-                    return *e;
-                }
-
-                if let Ok(e) = e.borrow::<AngleD>() {
-                    return e.to_anglef();
-                }
-
-                if let Ok(e) = e.borrow::<Number>() {
-                    return Angle::Radians(e.to_f32());
-                }
-
-                if let Ok(e) = e.borrow::<Integer>() {
-                    return Angle::Radians(*e as f32);
-                }
-
-                Angle::RAD_ZERO
-            }).unwrap_or(Angle::RAD_ZERO);
-            let pitch = pitch.map(|e| {
-                if let Ok(e) = e.borrow::<AngleF>() {
-                    return *e;
-                }
-
-                if let Ok(e) = e.borrow::<AngleD>() {
-                    return e.to_anglef();
-                }
-
-                if let Ok(e) = e.borrow::<Number>() {
-                    return Angle::Radians(e.to_f32());
-                }
-
-                if let Ok(e) = e.borrow::<Integer>() {
-                    return Angle::Radians(*e as f32);
-                }
-
-                Angle::RAD_ZERO
-            }).unwrap_or(Angle::RAD_ZERO);
-
-            let roll = roll.map(|e| {
-                if let Ok(e) = e.borrow::<AngleF>() {
-                    return *e;
-                }
-
-                if let Ok(e) = e.borrow::<AngleD>() {
-                    return e.to_anglef();
-                }
-
-                if let Ok(e) = e.borrow::<Number>() {
-                    return Angle::Radians(e.to_f32());
-                }
-
-                if let Ok(e) = e.borrow::<Integer>() {
-                    return Angle::Radians(*e as f32);
-                }
-
-                Angle::RAD_ZERO
-            }).unwrap_or(Angle::RAD_ZERO);
-
-            Ok(Self {
-                yaw,
-                pitch,
-                roll,
-                debug_flag: Self::DEBUG_FLAG_GREEK_A,
-            })
-        })?;
-        lua.globals().set("eulerf", t)
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, |a, b| a + b)
     }
 }
 
-use rlua::{
-    UserData,
-    UserDataMethods
-};
+///Component-wise, the result keeps the units and order of `self`.
+impl<S: Scalar> Sub<Self> for Euler<S> {
+    type Output = Self;
 
-impl UserData for EulerF {
-    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(_methods: &mut M) {
-        _methods.add_meta_method(MetaMethod::ToString, |_lua, this, ()| {
-            Ok(match this.debug_flag {
-                Euler::DEBUG_FLAG_GREEK_A => format!("[α: {}, β: {}, γ: {}]", this.roll, this.pitch, this.yaw),
-                Euler::DEBUG_FLAG_GREEK_B => format!("[ϕ: {}, θ: {}, ψ: {}]", this.roll, this.pitch, this.yaw),
-                _ => format!("<{}, {}, {}>", this.roll, this.pitch, this.yaw),
-            })
-        });
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, |a, b| a - b)
+    }
+}
+
+impl<S: Scalar> Mul<S> for Euler<S> {
+    type Output = Self;
+
+    fn mul(self, rhs: S) -> Self::Output {
+        self.map(|a| a.map(|s| s * rhs))
+    }
+}
+
+impl<S: Scalar> Neg for Euler<S> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|a| a.map(|s| -s))
+    }
+}
+
+///Each component turns the short way round.
+impl<S: Scalar> Interpolation<S> for Euler<S> {
+    fn lerp(a: Self, b: Self, t: S) -> Self {
+        a.zip(b, |a, b| Angle::lerp_shortest(a, b, t))
+    }
 
-        _methods.add_method_mut("debug_flag", |_lua, this, flag_value: Integer| {
-            this.debug_flag = flag_value as u8;
-            Ok(())
+    ///`f` receives the per component fraction of the shortest `a → v` over the shortest `a → b`, in radians.
+    ///A component that is the same in `a` and `b` gets a fraction of zero.
+    fn inverse_lerp<F: Fn(&Self) -> S>(a: Self, b: Self, v: Self, f: F) -> S {
+        let ratios = (v - a).wrapped().zip((b - a).wrapped(), |v, b| {
+            let span = b.take_radians();
+            Angle::Radians(crate::ternary!(span == S::ZERO => S::ZERO ; v.take_radians() / span))
         });
+        f(&ratios)
+    }
+}
 
-        _methods.add_method("to_quat", |_lua, this, ()| Ok(this.to_quat()));
+///Reads an angle from Lua. Plain numbers are radians.
+fn angle_from_lua<S: Scalar>(value: Option<mlua::Value>) -> mlua::Result<Angle<S>> {
+    match value {
+        None | Some(mlua::Value::Nil) => Ok(Angle::RAD_ZERO),
+        Some(mlua::Value::Integer(i)) => Ok(Angle::Radians(S::from_f64(i as f64))),
+        Some(mlua::Value::Number(n)) => Ok(Angle::Radians(S::from_f64(n))),
+        Some(mlua::Value::UserData(ud)) => {
+            if let Ok(a) = ud.borrow::<AngleF>() {
+                return Ok(match *a {
+                    Angle::Radians(r) => Angle::Radians(S::from_f32(r)),
+                    Angle::Degrees(d) => Angle::Degrees(S::from_f32(d)),
+                });
+            }
+            if let Ok(a) = ud.borrow::<AngleD>() {
+                return Ok(match *a {
+                    Angle::Radians(r) => Angle::Radians(S::from_f64(r)),
+                    Angle::Degrees(d) => Angle::Degrees(S::from_f64(d)),
+                });
+            }
+            if let Ok(a) = ud.borrow::<AngleX>() {
+                return Ok(match *a {
+                    Angle::Radians(r) => Angle::Radians(S::from_f64(r.to_f64())),
+                    Angle::Degrees(d) => Angle::Degrees(S::from_f64(d.to_f64())),
+                });
+            }
+            Err(mlua::Error::RuntimeError("expected an angle or a number".to_string()))
+        }
+        Some(other) => Err(mlua::Error::RuntimeError(format!("expected an angle or a number, found {}", other.type_name()))),
     }
-}
\ No newline at end of file
+}
+
+macro_rules! lua_euler {
+    ($t:ty => $f:ty, $name:literal) => {
+        impl $t {
+            ///Registers the `(yaw, pitch, roll, order)` constructor and its `_lerp` function.
+            pub fn from_lua(lua: &Lua) -> rlua::Result<()> {
+                type Args<'lua> = (Option<mlua::Value<'lua>>, Option<mlua::Value<'lua>>, Option<mlua::Value<'lua>>, Option<RotationOrder>);
+                let t = lua.create_function(|_, (yaw, pitch, roll, order): Args| {
+                    let mut euler = Self::new(angle_from_lua(yaw)?, angle_from_lua(pitch)?, angle_from_lua(roll)?)
+                        .with_order(order.unwrap_or_default());
+                    euler.debug_flag = Self::DEBUG_FLAG_GREEK_A;
+                    Ok(euler)
+                })?;
+                lua.globals().set($name, t)?;
+                lua.globals().set(
+                    concat!($name, "_lerp"),
+                    lua.create_function(|_, (a, b, t): (Self, Self, $f)| Ok(Self::lerp(a, b, t)))?,
+                )
+            }
+        }
+
+        impl UserData for $t {
+            fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(_methods: &mut M) {
+                _methods.add_meta_method(MetaMethod::ToString, |_lua, this, ()| {
+                    Ok(match this.debug_flag {
+                        Euler::<$f>::DEBUG_FLAG_GREEK_A => format!("[α: {}, β: {}, γ: {}]", this.roll, this.pitch, this.yaw),
+                        Euler::<$f>::DEBUG_FLAG_GREEK_B => format!("[ϕ: {}, θ: {}, ψ: {}]", this.roll, this.pitch, this.yaw),
+                        _ => format!("<{}, {}, {}>", this.roll, this.pitch, this.yaw),
+                    })
+                });
+                _methods.add_meta_method(MetaMethod::Eq, |_lua, this, b: Self| Ok(*this == b));
+                _methods.add_meta_method(MetaMethod::Add, |_lua, this, b: Self| Ok(*this + b));
+                _methods.add_meta_method(MetaMethod::Sub, |_lua, this, b: Self| Ok(*this - b));
+                _methods.add_meta_method(MetaMethod::Unm, |_lua, this, ()| Ok(-*this));
+                _methods.add_meta_function(MetaMethod::Mul, |lua, (a, b): (mlua::Value, mlua::Value)| {
+                    match (a, b) {
+                        (mlua::Value::UserData(e), s) | (s, mlua::Value::UserData(e)) => {
+                            Ok(*e.borrow::<Self>()? * <$f>::from_lua(s, lua)?)
+                        }
+                        (a, b) => Err(mlua::Error::RuntimeError(format!(
+                            "cannot multiply {} by {}", a.type_name(), b.type_name()
+                        ))),
+                    }
+                });
+
+                _methods.add_method_mut("debug_flag", |_lua, this, flag_value: Integer| {
+                    this.debug_flag = flag_value as u8;
+                    Ok(())
+                });
+
+                _methods.add_method("yaw", |_lua, this, ()| Ok(this.yaw));
+                _methods.add_method("pitch", |_lua, this, ()| Ok(this.pitch));
+                _methods.add_method("roll", |_lua, this, ()| Ok(this.roll));
+                _methods.add_method("order", |_lua, this, ()| Ok(format!("{:?}", this.order)));
+                _methods.add_method("with_order", |_lua, this, order: RotationOrder| Ok(this.with_order(order)));
+                _methods.add_method("wrapped", |_lua, this, ()| Ok(this.wrapped()));
+                _methods.add_method("to_quat", |_lua, this, ()| Ok(this.to_quat()));
+                _methods.add_method(format!("to_mat3x3{}", <$f>::NAME), |_lua, this, ()| Ok(this.to_matrix()));
+            }
+        }
+    };
+}
+
+use rlua::{
+    UserData,
+    UserDataMethods
+};
+
+lua_euler!(EulerF => f32, "eulerf");
+lua_euler!(EulerD => f64, "eulerd");
+lua_euler!(EulerX => Q16_16, "eulerx");
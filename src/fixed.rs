@@ -0,0 +1,559 @@
+use crate::interpolation::Interpolation;
+use crate::scalar::Scalar;
+use rlua::{FromLua, IntoLua, Lua, Value};
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+///Signed fixed-point number with 16 integer and 16 fractional bits.
+///Every operation is integer arithmetic, so results are bit-identical on every machine.
+///Values reach about ±32767, so a square saturates past about 181. Vector magnitudes and dot products
+///accumulate in 128 bits and stay exact for coordinates up to the full range, but products such as
+///`length` or matrix elements do not; use `Q32_32` for larger worlds.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Q16_16(i32);
+
+///Signed fixed-point number with 32 integer and 32 fractional bits.
+///Every operation is integer arithmetic, so results are bit-identical on every machine.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Q32_32(i64);
+
+///Transcendental functions on raw Q32.32 values, shared by both formats.
+///Trigonometry is accurate to about `1e-8`, `exp` and `ln` to about `1e-7`; arithmetic saturates instead of producing infinities or NaN.
+mod wide {
+    pub const FRAC: u32 = 32;
+    pub const ONE: i64 = 1 << FRAC;
+    pub const PI: i64 = 13_493_037_705;
+    pub const HALF_PI: i64 = PI / 2;
+    pub const TWO_PI: i64 = PI * 2;
+    pub const LN_2: i64 = 2_977_044_472;
+    pub const DEG_TO_RAD: i64 = 74_961_321;
+    pub const RAD_TO_DEG: i64 = 246_083_499_208;
+
+    ///`atan(2^-i)`.
+    const ATAN: [i64; 32] = [
+        3_373_259_426, 1_991_351_318, 1_052_175_346, 534_100_635, 268_086_748, 134_174_063, 67_103_403, 33_553_749,
+        16_777_131, 8_388_597, 4_194_303, 2_097_152, 1_048_576, 524_288, 262_144, 131_072, 65_536, 32_768, 16_384,
+        8_192, 4_096, 2_048, 1_024, 512, 256, 128, 64, 32, 16, 8, 4, 2,
+    ];
+    ///Product of `cos(atan(2^-i))` over the table, undoing the CORDIC gain.
+    const CORDIC_GAIN: i64 = 2_608_131_496;
+
+    pub fn saturate(v: i128) -> i64 {
+        v.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    pub fn mul(a: i64, b: i64) -> i64 {
+        saturate((a as i128 * b as i128 + (1 << (FRAC - 1))) >> FRAC)
+    }
+
+    pub fn div(a: i64, b: i64) -> i64 {
+        match b {
+            0 => a.signum() * i64::MAX,
+            _ => saturate(((a as i128) << FRAC) / b as i128),
+        }
+    }
+
+    pub fn sqrt(a: i64) -> i64 {
+        match a {
+            ..=0 => 0,
+            _ => (((a as u128) << FRAC).isqrt()) as i64,
+        }
+    }
+
+    pub fn hypot(a: i64, b: i64) -> i64 {
+        saturate(((a as i128).pow(2) as u128 + (b as i128).pow(2) as u128).isqrt() as i128)
+    }
+
+    ///CORDIC in rotation mode after reducing `angle` to `[-π/2, π/2]`.
+    pub fn sin_cos(angle: i64) -> (i64, i64) {
+        let mut angle = angle % TWO_PI;
+        if angle > PI {
+            angle -= TWO_PI;
+        } else if angle <= -PI {
+            angle += TWO_PI;
+        }
+
+        let mirrored = angle.abs() > HALF_PI;
+        if mirrored {
+            angle = angle.signum() * PI - angle;
+        }
+
+        let (mut x, mut y, mut z) = (CORDIC_GAIN, 0i64, angle);
+        for (i, step) in ATAN.iter().enumerate() {
+            let (dx, dy) = (y >> i, x >> i);
+            if z >= 0 {
+                (x, y, z) = (x - dx, y + dy, z - step);
+            } else {
+                (x, y, z) = (x + dx, y - dy, z + step);
+            }
+        }
+
+        (y, if mirrored { -x } else { x })
+    }
+
+    pub fn tan(angle: i64) -> i64 {
+        let (sin, cos) = sin_cos(angle);
+        div(sin, cos)
+    }
+
+    ///CORDIC in vectoring mode. The inputs are rescaled first so small vectors keep their precision.
+    pub fn atan2(y: i64, x: i64) -> i64 {
+        if x == 0 && y == 0 {
+            return 0;
+        }
+
+        let (mut x, mut y) = (x as i128, y as i128);
+        let bits = 128 - x.abs().max(y.abs()).leading_zeros() as i32;
+        if bits > 62 {
+            (x, y) = (x >> (bits - 62), y >> (bits - 62));
+        } else {
+            (x, y) = (x << (62 - bits), y << (62 - bits));
+        }
+
+        let mut z = 0;
+        if x < 0 {
+            (x, y, z) = if y >= 0 { (y, -x, HALF_PI) } else { (-y, x, -HALF_PI) };
+        }
+
+        for (i, step) in ATAN.iter().enumerate() {
+            let (dx, dy) = (y >> i, x >> i);
+            if y > 0 {
+                (x, y, z) = (x + dx, y - dy, z + step);
+            } else {
+                (x, y, z) = (x - dx, y + dy, z - step);
+            }
+        }
+        z
+    }
+
+    pub fn asin(s: i64) -> i64 {
+        let s = s.clamp(-ONE, ONE);
+        atan2(s, sqrt(ONE - mul(s, s)))
+    }
+
+    pub fn acos(c: i64) -> i64 {
+        let c = c.clamp(-ONE, ONE);
+        atan2(sqrt(ONE - mul(c, c)), c)
+    }
+
+    ///`e^r` with `|r| <= ln 2 / 2` from its Taylor series, then scaled by a power of two.
+    pub fn exp(a: i64) -> i64 {
+        if a > 22 * ONE {
+            return i64::MAX;
+        }
+        if a < -23 * ONE {
+            return 0;
+        }
+
+        let k = (a + a.signum() * LN_2 / 2) / LN_2;
+        let r = a - k * LN_2;
+        let (mut sum, mut term) = (ONE, ONE);
+        for n in 1..16 {
+            term = mul(term, r) / n;
+            sum += term;
+        }
+
+        match k {
+            0.. => saturate((sum as i128) << k),
+            _ => (sum + (1 << (-k - 1))) >> -k,
+        }
+    }
+
+    ///`k ln 2 + 2 atanh((m - 1) / (m + 1))` where `a = m 2^k` and `m` is in `[1, 2)`. Non-positive input gives the minimum.
+    pub fn ln(a: i64) -> i64 {
+        if a <= 0 {
+            return i64::MIN;
+        }
+
+        let k = (63 - a.leading_zeros() as i64) - FRAC as i64;
+        let m = match k {
+            0.. => a >> k,
+            _ => a << -k,
+        };
+
+        let z = div(m - ONE, m + ONE);
+        let z2 = mul(z, z);
+        let (mut sum, mut term) = (0, z);
+        for n in (1..40).step_by(2) {
+            sum += term / n;
+            term = mul(term, z2);
+        }
+
+        k * LN_2 + 2 * sum
+    }
+
+    ///Negative bases are only defined for whole exponents; otherwise the result is zero.
+    pub fn pow(base: i64, exponent: i64) -> i64 {
+        if exponent & (ONE - 1) == 0 {
+            return powi(base, (exponent >> FRAC) as i32);
+        }
+        match base {
+            ..=0 => 0,
+            _ => exp(mul(exponent, ln(base))),
+        }
+    }
+
+    pub fn powi(base: i64, exponent: i32) -> i64 {
+        let (mut result, mut base, mut n) = (ONE, base, exponent.unsigned_abs());
+        while n > 0 {
+            if n & 1 == 1 {
+                result = mul(result, base);
+            }
+            base = mul(base, base);
+            n >>= 1;
+        }
+        if exponent < 0 { div(ONE, result) } else { result }
+    }
+}
+
+macro_rules! fixed {
+    ($t:ident($i:ty, $w:ty) => $frac:literal, $name:literal) => {
+        impl $t {
+            pub const FRAC_BITS: u32 = $frac;
+            const HALF: $i = 1 << ($frac - 1);
+
+            ///Reinterprets `bits` as the raw fixed-point representation.
+            pub const fn from_bits(bits: $i) -> Self {
+                Self(bits)
+            }
+
+            pub const fn to_bits(self) -> $i {
+                self.0
+            }
+
+            const fn from_int(i: $i) -> Self {
+                Self(i << $frac)
+            }
+
+            ///Rounds the raw Q32.32 value to this precision, saturating on overflow.
+            const fn from_wide(w: i64) -> Self {
+                let shift = wide::FRAC - $frac;
+                let w = match shift {
+                    0 => w as i128,
+                    _ => (w as i128 + (1 << (shift - 1))) >> shift,
+                };
+                if w > <$i>::MAX as i128 {
+                    Self(<$i>::MAX)
+                } else if w < <$i>::MIN as i128 {
+                    Self(<$i>::MIN)
+                } else {
+                    Self(w as $i)
+                }
+            }
+
+            fn to_wide(self) -> i64 {
+                (self.0 as i64) << (wide::FRAC - $frac)
+            }
+
+            fn wide(self, f: impl Fn(i64) -> i64) -> Self {
+                Self::from_wide(f(self.to_wide()))
+            }
+
+            fn saturate(w: $w) -> Self {
+                Self(w.clamp(<$i>::MIN as $w, <$i>::MAX as $w) as $i)
+            }
+
+            fn trunc(self) -> Self {
+                match self.0 {
+                    0.. => self.s_floor(),
+                    _ => -(-self).s_floor(),
+                }
+            }
+        }
+
+        impl Scalar for $t {
+            const NEG_ONE: Self = Self::from_int(-1);
+            const ZERO: Self = Self::from_int(0);
+            const ONE: Self = Self::from_int(1);
+            const TWO: Self = Self::from_int(2);
+            const THREE: Self = Self::from_int(3);
+            const FOUR: Self = Self::from_int(4);
+            const FIVE: Self = Self::from_int(5);
+            const PI: Self = Self::from_wide(wide::PI);
+            const EPSILON: Self = Self(1);
+            ///Largest representable value, which overflowing operations saturate to.
+            const INFINITY: Self = Self(<$i>::MAX);
+            const NAME: &'static str = $name;
+
+            fn sine(self) -> Self {
+                self.wide(|a| wide::sin_cos(a).0)
+            }
+
+            fn cosine(self) -> Self {
+                self.wide(|a| wide::sin_cos(a).1)
+            }
+
+            fn tangent(self) -> Self {
+                self.wide(wide::tan)
+            }
+
+            fn inverse_sine(self) -> Self {
+                self.wide(wide::asin)
+            }
+
+            fn inverse_cosine(self) -> Self {
+                self.wide(wide::acos)
+            }
+
+            fn inverse_tangent(self) -> Self {
+                self.wide(|a| wide::atan2(a, wide::ONE))
+            }
+
+            fn inverse_tangent2(self, x: Self) -> Self {
+                Self::from_wide(wide::atan2(self.to_wide(), x.to_wide()))
+            }
+
+            fn power_i(self, i: i32) -> Self {
+                self.wide(|a| wide::powi(a, i))
+            }
+
+            fn power_f(self, f: Self) -> Self {
+                Self::from_wide(wide::pow(self.to_wide(), f.to_wide()))
+            }
+
+            fn square_root2(self) -> Self {
+                self.wide(wide::sqrt)
+            }
+
+            fn exponential(self) -> Self {
+                self.wide(wide::exp)
+            }
+
+            fn natural_logarithm(self) -> Self {
+                self.wide(wide::ln)
+            }
+
+            fn log(self, base: Self) -> Self {
+                Self::from_wide(wide::div(wide::ln(self.to_wide()), wide::ln(base.to_wide())))
+            }
+
+            fn min(self, other: Self) -> Self {
+                Ord::min(self, other)
+            }
+
+            fn max(self, other: Self) -> Self {
+                Ord::max(self, other)
+            }
+
+            fn round(self) -> Self {
+                match self.0 {
+                    0.. => Self(self.0.saturating_add(Self::HALF)).s_floor(),
+                    _ => -Self(self.0.saturating_neg().saturating_add(Self::HALF)).s_floor(),
+                }
+            }
+
+            fn fract(self) -> Self {
+                self - self.trunc()
+            }
+
+            ///Zero for zero, as there is no signed zero.
+            fn signum(self) -> Self {
+                Self::from_int(self.0.signum())
+            }
+
+            fn copysign(self, sign: Self) -> Self {
+                crate::ternary!(sign.0 < 0 => -self.s_abs() ; self.s_abs())
+            }
+
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                Self::saturate((self.0 as $w * a.0 as $w + ((b.0 as $w) << $frac) + Self::HALF as $w) >> $frac)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                Self::from_wide(wide::hypot(self.to_wide(), other.to_wide()))
+            }
+
+            ///Exact up to the final rounding, since the raw squares are summed in 128 bits.
+            fn euclidean_norm(values: &[Self]) -> Self {
+                let sum = values.iter().fold(0u128, |sum, v| sum.saturating_add((v.0 as i128).pow(2) as u128));
+                Self(sum.isqrt().min(<$i>::MAX as u128) as $i)
+            }
+
+            fn sum_of_products(a: &[Self], b: &[Self]) -> Self {
+                let sum = a.iter().zip(b).fold(0i128, |sum, (a, b)| sum.saturating_add(a.0 as i128 * b.0 as i128));
+                let w = sum.saturating_add(Self::HALF as i128) >> $frac;
+                Self(w.clamp(<$i>::MIN as i128, <$i>::MAX as i128) as $i)
+            }
+
+            fn is_nan(self) -> bool {
+                false
+            }
+
+            ///False once a value has saturated to either end of the range.
+            fn is_finite(self) -> bool {
+                self.0 > <$i>::MIN + 1 && self.0 < <$i>::MAX
+            }
+
+            fn from_f32(f: f32) -> Self {
+                Self::from_f64(f as f64)
+            }
+
+            fn from_f64(f: f64) -> Self {
+                Self((f * (1u64 << $frac) as f64).round() as $i)
+            }
+
+            fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            fn to_f64(self) -> f64 {
+                self.0 as f64 / (1u64 << $frac) as f64
+            }
+
+            fn rads(self) -> Self {
+                self.wide(|a| wide::mul(a, wide::DEG_TO_RAD))
+            }
+
+            fn degs(self) -> Self {
+                self.wide(|a| wide::mul(a, wide::RAD_TO_DEG))
+            }
+
+            fn s_clamp(self, min: Self, max: Self) -> Self {
+                Ord::clamp(self, min, max)
+            }
+
+            fn s_floor(self) -> Self {
+                Self(self.0 & !((1 << $frac) - 1))
+            }
+
+            fn s_ceil(self) -> Self {
+                -(-self).s_floor()
+            }
+
+            fn s_abs(self) -> Self {
+                Self(self.0.saturating_abs())
+            }
+
+            fn from_u8(v: u8) -> Self {
+                Self::from_int(v as $i)
+            }
+
+            fn to_u8(self) -> u8 {
+                (self.0 >> $frac).clamp(0, u8::MAX as $i) as u8
+            }
+        }
+
+        impl Add for $t {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self(self.0.saturating_add(rhs.0))
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self(self.0.saturating_sub(rhs.0))
+            }
+        }
+
+        ///Rounds to nearest.
+        impl Mul for $t {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self::saturate((self.0 as $w * rhs.0 as $w + Self::HALF as $w) >> $frac)
+            }
+        }
+
+        ///Rounds towards zero; dividing by zero saturates towards the sign of `self`.
+        impl Div for $t {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                match rhs.0 {
+                    0 => Self(self.0.signum() * <$i>::MAX),
+                    _ => Self::saturate(((self.0 as $w) << $frac) / rhs.0 as $w),
+                }
+            }
+        }
+
+        ///Takes the sign of `self`, like `f64`; a zero divisor gives zero.
+        impl Rem for $t {
+            type Output = Self;
+
+            fn rem(self, rhs: Self) -> Self::Output {
+                Self(self.0.checked_rem(rhs.0).unwrap_or(0))
+            }
+        }
+
+        impl Neg for $t {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self(self.0.saturating_neg())
+            }
+        }
+
+        impl AddAssign for $t {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $t {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for $t {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign for $t {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl Sum for $t {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, Add::add)
+            }
+        }
+
+        impl Interpolation<$t> for $t {
+            fn lerp(a: Self, b: Self, t: Self) -> Self {
+                a + (b - a) * t
+            }
+
+            fn inverse_lerp<F: Fn(&Self) -> Self>(a: Self, b: Self, v: Self, f: F) -> Self {
+                f(&((v - a) / (b - a)))
+            }
+        }
+
+        impl Display for $t {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(&self.to_f64(), f)
+            }
+        }
+
+        impl Debug for $t {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!($t), self.to_f64())
+            }
+        }
+
+        ///Lua numbers are rounded to the nearest representable value.
+        impl<'lua> FromLua<'lua> for $t {
+            fn from_lua(value: Value<'lua>, lua: &'lua Lua) -> rlua::Result<Self> {
+                Ok(Self::from_f64(f64::from_lua(value, lua)?))
+            }
+        }
+
+        impl<'lua> IntoLua<'lua> for $t {
+            fn into_lua(self, lua: &'lua Lua) -> rlua::Result<Value<'lua>> {
+                self.to_f64().into_lua(lua)
+            }
+        }
+    };
+}
+
+fixed!(Q16_16(i32, i64) => 16, "x");
+fixed!(Q32_32(i64, i128) => 32, "xl");
@@ -6,13 +6,18 @@ use std::time::{Duration, Instant};
 
 pub mod angle;
 pub mod euler;
+pub mod fixed;
 pub mod macros;
 pub mod matrix;
 pub mod prelude;
 pub mod quaternion;
+pub mod dual_quaternion;
+pub mod ray;
+pub mod rotation;
 pub mod scalar;
 pub mod vector;
 pub mod interpolation;
+mod lua;
 pub mod color;
 pub mod percentage;
 
@@ -43,19 +48,34 @@ pub trait LuaObject: UserData {
 
 #[cfg(test)]
 mod tests {
-    use crate::angle::Angle;
-    use crate::euler::EulerF;
-    use crate::matrix::matrix2x2::Matrix2x2F;
-    use crate::matrix::matrix3x3::Matrix3x3F;
-    use crate::quaternion::QuatF;
+    use crate::angle::{Angle, AngleD, AngleF, AngleUnit, AngleX, Dms};
+    use crate::euler::{Euler, EulerD, EulerF, EulerX};
+    use crate::matrix::matrix3x3::RotationOrder;
+    use crate::matrix::matrix2x2::{Matrix2x2D, Matrix2x2F};
+    use crate::matrix::matrix2x3::Matrix2x3D;
+    use crate::matrix::matrix3x3::{Matrix3x3, Matrix3x3D, Matrix3x3F, Matrix3x3X};
+    use crate::quaternion::{QuatD, QuatF, QuatX, Quaternion};
+    use crate::rotation::{AnyRotation, AxisAngleD, AxisAngleF, Rotation};
+    use crate::dual_quaternion::{DualQuatD, DualQuatF};
     use crate::vector::vec2::{Vec2D, Vec2F};
-    use crate::vector::vec3::{Vec3D, Vec3F};
-    use crate::vector::vec4::Vec4F;
+    use crate::vector::vec3::{Vec3, Vec3D, Vec3F, Vec3X};
+    use crate::vector::vec4::{Vec4D, Vec4F};
     use crate::LuaObject;
+    use crate::interpolation::Interpolation;
     use rlua::Lua;
     use crate::color::Color;
-    use crate::matrix::matrix4x4::Matrix4x4F;
+    use crate::fixed::{Q16_16, Q32_32};
+    use crate::scalar::{self, Scalar};
+    use crate::matrix::matrix4x4::{DecomposeError, Matrix4x4D, Matrix4x4F};
     use crate::percentage::Percentage;
+    use crate::matrix::convention::{MatrixConvention, CONVENTION};
+    use crate::matrix::square_matrix::SquareMatrix;
+    use crate::matrix::matrix3x4::Matrix3x4F;
+    use crate::matrix::matrix4x3::Matrix4x3F;
+    use crate::prelude::{vec2f, vec3f, vec4f};
+    use crate::matrix::dmatrix::{DMatrixD, DimensionMismatch};
+    use crate::vector::dvector::DVectorD;
+    use crate::matrix::projection::{project, screen_ray, unproject, Frustum, Handedness, ProjectionConfig, Viewport};
 
     #[test]
     fn test_hsv() {
@@ -69,6 +89,1135 @@ mod tests {
         println!("{:?}", rgb.to_rgb().to_hsv());
     }
 
+    fn assert_vec3_eq(a: Vec3F, b: Vec3F) {
+        assert!((a - b).magnitude() < 1e-5, "{a} != {b}");
+    }
+
+    #[test]
+    fn test_view_projection_pipeline() {
+        let view = Matrix4x4F::look_at(&vec3f(0.0, 0.0, 5.0), &Vec3F::ZERO, &Vec3F::UP);
+        let projection = Matrix4x4F::perspective(1.0, Angle::Degrees(90.0), 1.0, 10.0);
+
+        let pipeline = match CONVENTION {
+            MatrixConvention::RowMajor => projection * view,
+            MatrixConvention::ColumnMajor => view * projection,
+        };
+        assert_eq!(pipeline, view.then(projection));
+
+        //Reference values worked out by hand for the OpenGL view and projection matrices.
+        assert_vec3_eq(pipeline.transform_point(Vec3F::ZERO), vec3f(0.0, 0.0, 7.0 / 9.0));
+        assert_vec3_eq(pipeline.transform_point(vec3f(1.0, 1.0, 0.0)), vec3f(0.2, 0.2, 7.0 / 9.0));
+        assert_vec3_eq(pipeline.transform_point(vec3f(0.0, 0.0, 4.0)), vec3f(0.0, 0.0, -1.0));
+        assert_vec3_eq(pipeline.transform_point(vec3f(0.0, 0.0, -5.0)), vec3f(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_transform_composition() {
+        let m = Matrix4x4F::translation(vec3f(1.0, 0.0, 0.0)).then(Matrix4x4F::scale(Vec3F::ONE * 2.0));
+        assert_vec3_eq(m.transform_point(Vec3F::ZERO), vec3f(2.0, 0.0, 0.0));
+
+        let m = Matrix4x4F::rotation_z(Angle::Degrees(90.0)).then(Matrix4x4F::translation(vec3f(0.0, 0.0, 3.0)));
+        assert_vec3_eq(m.transform_point(Vec3F::RIGHT), vec3f(0.0, 1.0, 3.0));
+
+        let data = unsafe { std::slice::from_raw_parts(Matrix4x4F::translation(vec3f(1.0, 2.0, 3.0)).as_ptr(), 16) };
+        let translation = match CONVENTION.upload_transposed() {
+            true => [data[3], data[7], data[11]],
+            false => [data[12], data[13], data[14]],
+        };
+        assert_eq!(translation, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_point_vector_normal_transforms() {
+        let m = Matrix4x4F::scale(vec3f(2.0, 1.0, 1.0)).then(Matrix4x4F::translation(vec3f(0.0, 5.0, 0.0)));
+
+        assert_vec3_eq(m.transform_point(vec3f(1.0, 1.0, 1.0)), vec3f(2.0, 6.0, 1.0));
+        assert_vec3_eq(m.transform_vector(vec3f(1.0, 1.0, 1.0)), vec3f(2.0, 1.0, 1.0));
+
+        let normal = vec3f(1.0, 1.0, 0.0).normalized();
+        assert_vec3_eq(m.transform_normal(normal).unwrap(), vec3f(1.0, 2.0, 0.0).normalized());
+        assert!(Matrix4x4F::scale(vec3f(1.0, 0.0, 1.0)).transform_normal(normal).is_none());
+
+        let inverse = m.inverse().unwrap();
+        assert_vec3_eq(inverse.transform_point(vec3f(2.0, 6.0, 1.0)), vec3f(1.0, 1.0, 1.0));
+        assert!((m.determinant() - 2.0).abs() < 1e-6);
+        assert!(((m * inverse)[2] - Matrix4x4F::IDENTITY[2]).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn test_project_unproject() {
+        let eye = vec3f(0.0, 2.0, 5.0);
+        let view = Matrix4x4F::look_at(&eye, &Vec3F::ZERO, &Vec3F::UP);
+        let viewport = Viewport::new(0.0, 0.0, 800.0, 600.0);
+
+        let configs = [ProjectionConfig::OPENGL, ProjectionConfig::VULKAN.reversed()];
+        for config in configs {
+            let projection = Matrix4x4F::perspective_with(800.0 / 600.0, Angle::Degrees(60.0), 0.1, None, config);
+            let viewport = viewport.with_clip(config);
+
+            let screen = project(Vec3F::ZERO, &view, &projection, &viewport);
+            assert!((screen[0] - 400.0).abs() < 1e-2 && (screen[1] - 300.0).abs() < 1e-2, "{screen}");
+
+            let world = unproject(screen, &view, &projection, &viewport).unwrap();
+            assert!(world.magnitude() < 1e-2, "{world}");
+
+            let ray = screen_ray(vec2f(400.0, 300.0), &view, &projection, &viewport).unwrap();
+            assert_vec3_eq(ray.direction, (-eye).normalized());
+            assert!((ray.origin - eye).magnitude() < 0.2);
+        }
+
+        let lua = Lua::new();
+        Vec2D::load_lua(&lua).unwrap();
+        Vec3D::load_lua(&lua).unwrap();
+        Vec4D::load_lua(&lua).unwrap();
+        Matrix4x4D::load_lua(&lua).unwrap();
+        Angle::<f64>::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local view = mat4x4d_look_at(vec3d(0, 2, 5), vec3d(0, 0, 0), vec3d(0, 1, 0))
+            local viewport = vec4d(0, 0, 800, 600)
+            local point = vec3d(0.5, -0.25, 1)
+            for _, config in ipairs({"opengl", "vulkan", "vulkan_reversed"}) do
+                local projection = mat4x4d_perspective_with(800 / 600, deg(60), 0.1, 100, config)
+                local screen = mat4x4d_project(point, view, projection, viewport, config)
+                assert((mat4x4d_unproject(screen, view, projection, viewport, config) - point):magnitude() < 1e-6)
+                local _, direction = mat4x4d_screen_ray(vec2d(400, 300), view, projection, viewport, config)
+                assert(direction:dot(vec3d(0, -2, -5):norm()) > 1 - 1e-9)
+            end
+            local projection = mat4x4d_perspective(800 / 600, deg(60), 0.1, 100)
+            local screen = mat4x4d_project(point, view, projection, viewport)
+            assert((mat4x4d_unproject(screen, view, projection, viewport) - point):magnitude() < 1e-6)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_decompose() {
+        let rotation = Matrix4x4F::rotation_axis(vec3f(1.0, 2.0, 3.0), Angle::Degrees(40.0));
+        let m = Matrix4x4F::scale(vec3f(-2.0, 3.0, 0.5))
+            .then(rotation)
+            .then(Matrix4x4F::translation(vec3f(4.0, -5.0, 6.0)));
+
+        let (t, r, s) = m.decompose().unwrap();
+        assert_vec3_eq(t, vec3f(4.0, -5.0, 6.0));
+        assert_vec3_eq(s, vec3f(-2.0, 3.0, 0.5));
+
+        let rebuilt = Matrix4x4F::from_trs(t, r, s);
+        for row in 0..4 {
+            assert!((rebuilt[row] - m[row]).magnitude() < 1e-5, "{rebuilt}\n!=\n{m}");
+        }
+
+        let sheared = Matrix4x4F::shear(0.5, 0.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(sheared.try_decompose(), Err(DecomposeError::Shear));
+
+        let projection = Matrix4x4F::perspective(1.0, Angle::Degrees(90.0), 1.0, 10.0);
+        assert_eq!(projection.try_decompose(), Err(DecomposeError::Perspective));
+    }
+
+    #[test]
+    fn test_projection_variants() {
+        let configs = [
+            ProjectionConfig::OPENGL,
+            ProjectionConfig::VULKAN,
+            ProjectionConfig::DIRECTX,
+            ProjectionConfig::VULKAN.reversed(),
+            ProjectionConfig::DIRECTX.reversed(),
+        ];
+
+        for config in configs {
+            let forward = match config.handedness {
+                Handedness::Right => -1.0,
+                Handedness::Left => 1.0,
+            };
+            let (low, high) = config.depth_bounds::<f32>();
+
+            for far in [Some(50.0), None] {
+                let frustum = Frustum { left: -1.0, right: 2.0, bottom: -0.5, top: 1.5, near: 0.5, far };
+                let m = frustum.matrix(config);
+
+                let near_point = m.transform_point(vec3f(0.0, 0.0, 0.5 * forward));
+                assert!((near_point[2] - low).abs() < 1e-5, "{config:?}: {near_point}");
+                if far.is_some() {
+                    let far_point = m.transform_point(vec3f(0.0, 0.0, 50.0 * forward));
+                    assert!((far_point[2] - high).abs() < 1e-4, "{config:?}: {far_point}");
+                }
+
+                let p = vec3f(0.3, -0.2, 7.0 * forward);
+                let ndc = m.transform_point(p);
+                assert!((frustum.unproject(ndc, config) - p).magnitude() < 1e-3, "{config:?}");
+            }
+        }
+
+        let lua = Lua::new();
+        Matrix4x4D::load_lua(&lua).unwrap();
+        Angle::<f64>::load_lua(&lua).unwrap();
+        let (frustum, perspective): (Matrix4x4D, Matrix4x4D) = lua
+            .load(
+                r#"
+                assert(not pcall(mat4x4d_perspective_with, 1, deg(90), 1, 10, "metal"))
+                return mat4x4d_frustum_with(-1, 2, -0.5, 1.5, 0.5, nil, "vulkan_reversed"),
+                    mat4x4d_perspective_with(1, deg(90), 1, 10, "DirectX")
+                "#,
+            )
+            .eval()
+            .unwrap();
+        let config = ProjectionConfig::VULKAN.reversed();
+        assert_eq!(frustum, Matrix4x4D::frustum_with(-1.0, 2.0, -0.5, 1.5, 0.5, None, config));
+        let config = ProjectionConfig::DIRECTX;
+        assert_eq!(perspective, Matrix4x4D::perspective_with(1.0, Angle::Degrees(90.0), 1.0, Some(10.0), config));
+    }
+
+    #[test]
+    fn test_matrix_lua_metamethods() {
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        Matrix3x3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+
+        lua.load(
+            r#"
+            local a = mat3x3f(vec3f(1, 2, 3), vec3f(4, 5, 6), vec3f(7, 8, 9))
+            local b = mat3x3f_identity()
+
+            assert((a + b)[0] == vec3f(2, 2, 3))
+            assert((a - b)[2] == vec3f(7, 8, 8))
+            assert((-a)[1] == vec3f(-4, -5, -6))
+            assert(a * b == a)
+            assert(2 * a == a * 2)
+            assert((a / 2)[0] == vec3f(0.5, 1, 1.5))
+            assert(a:row(1) == vec3f(4, 5, 6))
+            assert(a:col(1) == vec3f(2, 5, 8))
+            assert(a ~= b)
+            assert(not pcall(function() return b:row(5) end) and not pcall(function() return b:col(5) end))
+            assert(a * vec3f(1, 0, -1) == vec3f(-2, -2, -2))
+            local ok, message = pcall(function() return a * quatf() end)
+            assert(not ok and string.find(tostring(message), "Quaternion", 1, true))
+            assert(not pcall(function() return b[3] end) and not pcall(function() b:set(3, 0, 1) end))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_non_square_matrices() {
+        let bone = Matrix3x4F::from_array([
+            vec4f(1.0, 0.0, 0.0, 5.0),
+            vec4f(0.0, 2.0, 0.0, 0.0),
+            vec4f(0.0, 0.0, 1.0, -1.0),
+        ]);
+
+        assert_vec3_eq(bone.transform_point(Vec3F::ONE), vec3f(6.0, 2.0, 0.0));
+        assert_vec3_eq(bone.transform_vector(Vec3F::ONE), vec3f(1.0, 2.0, 1.0));
+        assert_vec3_eq(bone.transpose().transform_point(Vec3F::ONE), vec3f(6.0, 2.0, 0.0));
+        assert_eq!(bone.to_4x4().conventional()[3], Vec4F::from_array([0.0, 0.0, 0.0, 1.0]));
+        assert_eq!(Matrix3x4F::from_4x4(bone.to_4x4()), bone);
+        assert_eq!(Matrix4x3F::from_4x4(bone.to_4x4()), bone.transpose());
+        assert_eq!(Matrix4x3F::from_4x4(bone.to_4x4()).to_4x4(), bone.to_4x4());
+        let moved = Matrix4x4F::translation(vec3f(5.0, 0.0, -1.0));
+        assert_vec3_eq(Matrix3x4F::from_4x4(moved).transform_point(Vec3F::ONE), moved.transform_point(Vec3F::ONE));
+        assert_vec3_eq(Matrix4x3F::from_4x4(moved).transform_point(Vec3F::ONE), moved.transform_point(Vec3F::ONE));
+        assert_vec3_eq(bone.to_4x4().transform_point(Vec3F::ONE), vec3f(6.0, 2.0, 0.0));
+
+        assert_eq!(bone * Matrix4x4F::IDENTITY, bone);
+        assert_eq!(Matrix3x3F::IDENTITY * bone, bone);
+        assert_eq!((bone * bone.transpose())[1], vec3f(0.0, 4.0, 0.0));
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        Vec4F::load_lua(&lua).unwrap();
+        Matrix3x3F::load_lua(&lua).unwrap();
+        Matrix3x4F::load_lua(&lua).unwrap();
+        Matrix4x3F::load_lua(&lua).unwrap();
+        Matrix4x4F::load_lua(&lua).unwrap();
+
+        lua.load(
+            r#"
+            local bone = mat3x4f(vec4f(1, 0, 0, 5), vec4f(0, 2, 0, 0), vec4f(0, 0, 1, -1))
+            assert(bone * mat4x4f_identity() == bone)
+            assert(mat3x3f_identity() * bone == bone)
+            assert((bone * bone:transpose()):row(1) == vec3f(0, 4, 0))
+            assert(mat3x4f_from_mat4x4(bone:to_mat4x4()) == bone)
+            assert(bone * vec4f(1, 1, 1, 1) == vec3f(6, 2, 0) and mat4x4f_identity() * vec4f(1, 2, 3, 1) == vec4f(1, 2, 3, 1))
+            assert(mat3x4f_from_mat4x4(mat4x4f_translation(vec3f(5, 0, -1))):col(3) == vec3f(5, 0, -1))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_dynamic_matrices() {
+        let m = DMatrixD::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        let v = DVectorD::from_vec(vec![1.0, 0.0, -1.0]);
+
+        assert_eq!((m.clone() * v.clone()).as_slice(), &[-2.0, -2.0]);
+        assert_eq!(m.try_mul(&m.transpose()).unwrap().as_slice(), &[14.0, 32.0, 32.0, 77.0]);
+        assert_eq!(
+            m.try_mul(&m),
+            Err(DimensionMismatch { expected: (3, 3), found: (2, 3) })
+        );
+        assert_eq!(v.dot_product(&DVectorD::zeros(2)).unwrap_err().found, (2, 1));
+
+        let fixed = Matrix4x4D::translation(Vec3D::from_array([1.0, 2.0, 3.0]));
+        let dynamic = DMatrixD::from(fixed);
+        assert_eq!(dynamic.determinant().unwrap(), fixed.determinant());
+        assert_eq!(Matrix4x4D::try_from(dynamic.inverse().unwrap().unwrap()), Ok(fixed.inverse().unwrap()));
+        assert!(Matrix3x3D::try_from(dynamic.clone()).is_err());
+        assert!(dynamic.determinant().is_ok() && m.determinant().is_err());
+        assert_eq!(Vec3D::try_from(DVectorD::from(Vec3D::ONE)), Ok(Vec3D::ONE));
+
+        let lua = Lua::new();
+        Vec3D::load_lua(&lua).unwrap();
+        Matrix3x3D::load_lua(&lua).unwrap();
+        DMatrixD::load_lua(&lua).unwrap();
+        DVectorD::load_lua(&lua).unwrap();
+
+        lua.load(
+            r#"
+            local m = matd(2, 3, {1, 2, 3, 4, 5, 6})
+            local v = vecd({1, 0, -1})
+            assert(m:rows() == 2 and m:cols() == 3 and m:get(2, 1) == 6)
+            assert(m * v == vecd({-2, -2}))
+            assert((m * m:transpose()):determinant() == 54)
+            assert(2 * v == v * 2 and -v == v / -1 and #vecd(5) == 5)
+            assert((m + m) == m * 2 and (m - m) == matd(2, 3))
+            assert(not pcall(function() return m * m end))
+            assert(not pcall(function() return m[2] end) and not pcall(function() return m:get(3, 0) end))
+            assert(not pcall(function() m:set(0, 2, 1) end) and not pcall(function() return m:col(3) end))
+            assert(not pcall(function() v:set(3, 1) end) and v[3] == nil)
+
+            local i = matd_identity(3)
+            i:set(0, 1, 4)
+            assert(i:to_fixed():row(1) == vec3d(4, 1, 0))
+            assert(matd_from(mat3x3d_identity()) == matd_identity(3))
+            assert(vecd_from(vec3d(1, 2, 3)):to_fixed() == vec3d(1, 2, 3))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_matrix_functions() {
+        let close = |a: Matrix3x3D, b: Matrix3x3D| (a - b).frobenius_norm() < 1e-9;
+        let m = Matrix3x3D::from_array([
+            Vec3D::from_array([1.0, -2.0, 0.0]),
+            Vec3D::from_array([3.0, 4.0, 1.0]),
+            Vec3D::from_array([0.0, 0.0, 2.0]),
+        ]);
+
+        assert_eq!(m.norm_1(), 6.0);
+        assert_eq!(m.norm_inf(), 8.0);
+        assert!((m.frobenius_norm() - 35f64.sqrt()).abs() < 1e-12);
+        assert!((Matrix2x2D::identity(Some(-3.0)).spectral_norm() - 3.0).abs() < 1e-9);
+        assert_eq!(m.rank(), 3);
+        assert_eq!(Matrix3x3D::ONE.rank(), 1);
+        assert_eq!(m.hadamard(&Matrix3x3D::ONE), m);
+        assert_eq!(DMatrixD::from_vec(2, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0]).unwrap().rank(), 1);
+
+        let k = Matrix2x2D::identity(Some(2.0)).kronecker(&Matrix2x3D::ONE);
+        assert_eq!((k[1][2], k[2][2], k[3][4]), (2.0, 0.0, 2.0));
+
+        assert!(close(m.power(3).unwrap(), m * m * m));
+        assert!(close(m.power(-2).unwrap() * m * m, Matrix3x3D::IDENTITY));
+        assert_eq!(m.power(0), Some(Matrix3x3D::IDENTITY));
+
+        //exp of a skew symmetric generator is a rotation.
+        let generator = Matrix2x2D::from_array([
+            Vec2D::from_array([0.0, -1.0]),
+            Vec2D::from_array([1.0, 0.0]),
+        ]);
+        let rotation = (generator * std::f64::consts::FRAC_PI_2).exp();
+        assert!((rotation - generator).frobenius_norm() < 1e-9);
+        assert!(((generator * 0.5).exp().ln().unwrap() - generator * 0.5).frobenius_norm() < 1e-9);
+
+        let root = m.sqrt().unwrap();
+        assert!(close(root * root, m));
+        assert!(close(m.ln().unwrap().exp(), m));
+        assert!(Matrix2x2D::identity(Some(-1.0)).sqrt().is_none());
+        let negative = Matrix2x2D::from_array([Vec2D::from_array([-2.0, 0.0]), Vec2D::from_array([0.0, -3.0])]);
+        assert!(negative.sqrt().is_none() && negative.ln().is_none());
+        let infinite = Matrix2x2D::identity(Some(f64::INFINITY));
+        assert!(infinite.exp().as_slice().iter().all(|s| s.is_nan()) && infinite.ln().is_none());
+
+        let lua = Lua::new();
+        Matrix2x2D::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local m = mat2x2d_identity(2)
+            assert(m:power(-1) == mat2x2d_identity(0.5))
+            assert(m:norm_1() == 2 and m:rank() == 2 and m:hadamard(m) == mat2x2d_identity(4))
+            assert((m:sqrt() - mat2x2d_identity(math.sqrt(2))):frobenius_norm() < 1e-9)
+            assert((m:ln():exp() - m):frobenius_norm() < 1e-9)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_quaternion_interpolation() {
+        let about_z = |degrees: f64| {
+            let half = degrees.to_radians() / 2.0;
+            QuatD { w: half.cos(), xyz: Vec3D::from_array([0.0, 0.0, half.sin()]) }
+        };
+        let close = |a: QuatD, b: QuatD| (a.dot(b).abs() - 1.0).abs() < 1e-9;
+        let (a, b) = (about_z(0.0), about_z(90.0));
+
+        assert!(close(QuatD::slerp(a, b, 0.5), about_z(45.0)));
+        assert!(close(QuatD::slerp(a, -b, 0.25), about_z(22.5)));
+        assert!(close(QuatD::lerp(a, b, 1.0), b));
+        assert!(close(QuatD::nlerp(a, b, 0.5), about_z(45.0)));
+        assert!((QuatD::nlerp(a, b, 0.3).magnitude() - 1.0).abs() < 1e-12);
+        assert!((QuatD::inverse_lerp(a, b, about_z(30.0), |q| q.w) - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(QuatD::inverse_lerp(a, a, about_z(30.0), |q| q.w), 0.0);
+        assert!(close(b.ln().exp(), b));
+
+        //Keys at a constant angular velocity give controls on the same arc, so squad matches slerp.
+        let keys = [about_z(-90.0), a, b, about_z(180.0)];
+        let a_control = QuatD::squad_control(keys[0], keys[1], keys[2]);
+        let b_control = QuatD::squad_control(keys[1], keys[2], keys[3]);
+        assert!(close(a_control, a) && close(b_control, b));
+        for t in [0.0, 0.2, 0.5, 1.0] {
+            assert!(close(QuatD::squad(a, b, a_control, b_control, t), QuatD::slerp(a, b, t)));
+        }
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local a = quatf_identity()
+            local b = quatf(0, vec3f(0, 0, 1))
+            local mid = quatf_slerp(a, b, 0.5)
+            assert(math.abs(mid:magnitude() - 1) < 1e-5)
+            assert(tostring(quatf_nlerp(a, b, 0)) == tostring(a))
+            assert(tostring(quatf_squad(a, b, quatf_squad_control(a, a, b), quatf_squad_control(a, b, b), 0)) == tostring(a))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_quaternion_constructors() {
+        let (x, y, z) = (vec3f(1.0, 0.0, 0.0), vec3f(0.0, 1.0, 0.0), vec3f(0.0, 0.0, 1.0));
+
+        let q = QuatF::from_axis_angle(z * 2.0, Angle::Degrees(90.0));
+        assert_vec3_eq(q * x, y);
+        let (axis, angle) = q.to_axis_angle();
+        assert_vec3_eq(axis, z);
+        assert!((angle.take_degrees() - 90.0).abs() < 1e-4);
+        assert_eq!(QuatF::IDENTITY.to_axis_angle(), (x, Angle::Radians(0.0)));
+
+        let from = vec3f(1.0, 2.0, -0.5);
+        let to = vec3f(-3.0, 0.5, 1.0);
+        assert_vec3_eq(QuatF::from_rotation_arc(from, to) * from.normalized(), to.normalized());
+        assert_vec3_eq(QuatF::from_rotation_arc(y, -y) * y, -y);
+
+        let forward = vec3f(1.0, 0.0, -1.0).normalized();
+        let look = QuatF::look_rotation(forward, y);
+        assert_vec3_eq(look * -z, forward);
+        assert_vec3_eq(look * y, y);
+        let view = Matrix4x4F::look_at(&Vec3F::ZERO, &forward, &y);
+        assert_vec3_eq(view.transform_vector(look * x), x);
+
+        let back = QuatF::from_rotation_matrix(look.to_rotation_3x3());
+        assert!((back.dot(look).abs() - 1.0).abs() < 1e-5);
+        for up in [y, x] {
+            let degenerate = QuatF::look_rotation(up * 2.0, up);
+            assert!(!degenerate.w.is_nan() && (degenerate.magnitude() - 1.0).abs() < 1e-5);
+            assert_vec3_eq(degenerate * -z, up);
+        }
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        Matrix3x3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+        AngleF::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            assert(tostring(quatf()) == tostring(quatf_identity()))
+            local q = quatf_from_axis_angle(vec3f(0, 0, 1), degf(90))
+            local function is_quarter_turn_about_z(q)
+                local axis, angle = q:to_axis_angle()
+                return math.abs(angle:to_deg():take() - 90) < 1e-3 and axis:dot(vec3f(0, 0, 1)) > 0.999
+            end
+            assert(is_quarter_turn_about_z(q))
+            assert(is_quarter_turn_about_z(quatf_from_rotation_arc(vec3f(1, 0, 0), vec3f(0, 1, 0))))
+            assert(is_quarter_turn_about_z(quatf_from_rotation_matrix(q:to_mat3x3f())))
+            local _, angle = quatf_look_rotation(vec3f(0, 0, -1), vec3f(0, 1, 0)):to_axis_angle()
+            assert(angle:take() < 1e-3)
+            local _, degenerate = quatf_look_rotation(vec3f(0, 1, 0), vec3f(0, 1, 0)):to_axis_angle()
+            assert(degenerate:take() == degenerate:take())
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_quaternion_api() {
+        let q = QuatF::from_axis_angle(Vec3F::UP, Angle::Degrees(90.0));
+        assert_vec3_eq(q.forward(), Vec3F::RIGHT);
+        assert_vec3_eq(q.right(), -Vec3F::FORWARD);
+        assert_vec3_eq(q.up(), Vec3F::UP);
+        assert_vec3_eq(q.rotate(Vec3F::FORWARD), q * Vec3F::FORWARD);
+        assert_vec3_eq(QuatF::look_rotation(q.forward(), q.up()) * Vec3F::BACK, q.forward());
+        assert!((q.angle_to(QuatF::IDENTITY).take_degrees() - 90.0).abs() < 1e-3);
+        assert!((q.angle_to(-q).take()).abs() < 1e-3);
+
+        let mut scaled = q * 3.0;
+        assert!(q.is_normalized() && !scaled.is_normalized());
+        scaled.normalize();
+        assert!(scaled.is_normalized());
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+        AngleF::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local q = quatf_from_axis_angle(vec3f(0, 1, 0), degf(90))
+            local v = q * vec3f(0, 0, 1)
+            assert((v - vec3f(1, 0, 0)):magnitude() < 1e-5)
+            assert((q:rotate(vec3f(0, 0, 1)) - q:forward()):magnitude() < 1e-5)
+            assert((q:right() - vec3f(0, 0, -1)):magnitude() < 1e-5 and (q:up() - vec3f(0, 1, 0)):magnitude() < 1e-5)
+            assert(math.abs(q:angle_to(quatf()):to_deg():take() - 90) < 1e-3)
+            assert(math.abs(q:dot(q) - 1) < 1e-5)
+
+            local s = 2 * q * 2
+            assert(not s:is_normalized() and s:normalized():is_normalized())
+            s:normalize()
+            assert(s:is_normalized() and q * quatf() == q)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_quaternion_integration() {
+        let same_rotation = |a: QuatD, b: QuatD| (a.dot(b).abs() - 1.0).abs() < 1e-9;
+        let q = QuatD::from_axis_angle(Vec3D::from_array([1.0, 2.0, 3.0]), Angle::Degrees(120.0));
+
+        assert!(same_rotation(q.pow(0.5) * q.pow(0.5), q));
+        assert!((q.pow(0.25).angle_to(QuatD::IDENTITY).take_degrees() - 30.0).abs() < 1e-9);
+        assert!(same_rotation(q.pow(1.0), q) && same_rotation(q.pow(0.0), QuatD::IDENTITY));
+
+        let omega = Vec3D::from_array([0.0, 0.0, std::f64::consts::PI]);
+        assert!(same_rotation(
+            QuatD::from_angular_velocity(omega, 0.5),
+            QuatD::from_axis_angle(Vec3D::FORWARD, Angle::Degrees(90.0))
+        ));
+
+        let mut orientation = q;
+        for _ in 0..1000 {
+            orientation = orientation.integrate(omega, 0.001);
+        }
+        assert!(orientation.is_normalized());
+        assert!(same_rotation(orientation, QuatD::from_angular_velocity(omega, 1.0) * q));
+
+        let omega = Vec3D::from_array([0.3, -1.2, 0.8]);
+        let next = q.integrate(omega, 0.1);
+        let measured = QuatD::angular_velocity_between(q, next, 0.1);
+        assert!((measured - omega).magnitude() < 1e-9);
+        assert!((QuatD::angular_velocity_between(q, -next, 0.1) - omega).magnitude() < 1e-9);
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local omega = vec3f(0, 2, 0)
+            local q = quatf():integrate(omega, 0.25)
+            local back = quatf_angular_velocity_between(quatf(), q, 0.25)
+            assert((back - omega):magnitude() < 1e-4)
+            assert(math.abs(q:dot(quatf_from_angular_velocity(omega, 0.25)) - 1) < 1e-5)
+            assert(math.abs(q:pow(2):dot(q * q) - 1) < 1e-5)
+            assert(math.abs(q:ln():exp():dot(q) - 1) < 1e-5)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_dual_quaternions() {
+        let close = |a: Vec3D, b: Vec3D| (a - b).magnitude() < 1e-9;
+        let p = Vec3D::from_array([0.5, -1.0, 2.0]);
+        let a = DualQuatD::from_rotation_translation(
+            QuatD::from_axis_angle(Vec3D::from_array([1.0, 1.0, 0.0]), Angle::Degrees(60.0)),
+            Vec3D::from_array([1.0, 2.0, 3.0]),
+        );
+        let b = DualQuatD::from_rotation_translation(
+            QuatD::from_axis_angle(Vec3D::FORWARD, Angle::Degrees(-30.0)),
+            Vec3D::from_array([-4.0, 0.0, 1.0]),
+        );
+
+        let m = Matrix4x4D::from_trs(a.translation(), a.rotation(), Vec3D::ONE);
+        assert!(close(a.transform_point(p), m.transform_point(p)));
+        assert!((a.to_matrix() - m).frobenius_norm() < 1e-9);
+        assert!(close(DualQuatD::from_matrix(m).unwrap().transform_point(p), a.transform_point(p)));
+
+        assert!(close((a * b).transform_point(p), a.transform_point(b.transform_point(p))));
+        assert!(close(a.inverse().transform_point(a.transform_point(p)), p));
+        assert!(close(a.conjugate().transform_point(a.transform_point(p)), p));
+        assert!(close(a.transform_vector(p), a.rotation() * p));
+
+        assert!(close(DualQuatD::sclerp(a, b, 0.0).transform_point(p), a.transform_point(p)));
+        assert!(close(DualQuatD::sclerp(a, b, 1.0).transform_point(p), b.transform_point(p)));
+        assert!(close(DualQuatD::sclerp(a, -b, 1.0).transform_point(p), b.transform_point(p)));
+
+        //A screw about and along Z: half way is half the turn and half the rise.
+        let screw = DualQuatD::from_rotation_translation(
+            QuatD::from_axis_angle(Vec3D::FORWARD, Angle::Degrees(90.0)),
+            Vec3D::from_array([0.0, 0.0, 2.0]),
+        );
+        let half = DualQuatD::sclerp(DualQuatD::IDENTITY, screw, 0.5);
+        assert!(close(half.translation(), Vec3D::from_array([0.0, 0.0, 1.0])));
+        assert!((half.rotation().angle_to(QuatD::IDENTITY).take_degrees() - 45.0).abs() < 1e-9);
+        let slide = DualQuatD::from_translation(Vec3D::ONE);
+        assert!(close(DualQuatD::sclerp(DualQuatD::IDENTITY, slide, 0.25).translation(), Vec3D::ONE * 0.25));
+
+        let twist = |degrees: f64| DualQuatD::from_rotation(QuatD::from_axis_angle(Vec3D::RIGHT, Angle::Degrees(degrees)));
+        let blended = DualQuatD::dlb(&[(twist(170.0), 0.5), (twist(-170.0), 0.5)]);
+        let q = Vec3D::from_array([0.0, 1.0, 0.0]);
+        assert!((blended.transform_point(q).magnitude() - 1.0).abs() < 1e-9);
+        assert!(close(
+            DualQuatD::dlb(&[(slide, 0.5), (DualQuatD::IDENTITY, 0.5)]).translation(),
+            Vec3D::ONE * 0.5
+        ));
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+        AngleF::load_lua(&lua).unwrap();
+        Matrix4x4F::load_lua(&lua).unwrap();
+        DualQuatF::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local a = dualquatf(quatf_from_axis_angle(vec3f(0, 0, 1), degf(90)), vec3f(1, 0, 0))
+            assert((a:transform_point(vec3f(1, 0, 0)) - vec3f(1, 1, 0)):magnitude() < 1e-5)
+            assert(((a * a:inverse()):translation()):magnitude() < 1e-5)
+            local m = a:to_mat4x4f()
+            assert((dualquatf_from_matrix(m):translation() - vec3f(1, 0, 0)):magnitude() < 1e-5)
+            local half = dualquatf_sclerp(dualquatf_identity(), a, 0.5)
+            local _, angle = half:rotation():to_axis_angle()
+            assert(math.abs(angle:to_deg():take() - 45) < 1e-3)
+            local blend = dualquatf_dlb({a, dualquatf()}, {0.5, 0.5})
+            assert(math.abs(blend:rotation():magnitude() - 1) < 1e-5)
+            assert(not pcall(dualquatf_dlb, {a}, {}))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_swing_twist() {
+        let same_rotation = |a: QuatD, b: QuatD| (a.dot(b).abs() - 1.0).abs() < 1e-9;
+        let axis = Vec3D::from_array([0.0, 1.0, 0.0]);
+        let twist = QuatD::from_axis_angle(axis, Angle::Degrees(70.0));
+        let swing = QuatD::from_axis_angle(Vec3D::from_array([1.0, 0.0, 1.0]), Angle::Degrees(50.0));
+        let q = swing * twist;
+
+        let (s, t) = q.swing_twist(axis);
+        assert!(same_rotation(s, swing) && same_rotation(t, twist));
+        assert!(same_rotation(s * t, q));
+        assert!((q.twist_angle(axis).take_degrees() - 70.0).abs() < 1e-9);
+        assert!((q.twist_angle(-axis).take_degrees() + 70.0).abs() < 1e-9);
+        let (_, none) = QuatD::from_axis_angle(Vec3D::RIGHT, Angle::Degrees(180.0)).swing_twist(axis);
+        assert_eq!(none, QuatD::IDENTITY);
+
+        assert!(same_rotation(swing.clamp_angle(Angle::Degrees(90.0)), swing));
+        assert!((swing.clamp_angle(Angle::Degrees(20.0)).angle_to(QuatD::IDENTITY).take_degrees() - 20.0).abs() < 1e-9);
+
+        let limited = q.clamp_swing_twist(axis, Angle::Degrees(30.0), Angle::Degrees(-45.0), Angle::Degrees(45.0));
+        let (s, _) = limited.swing_twist(axis);
+        assert!((s.angle_to(QuatD::IDENTITY).take_degrees() - 30.0).abs() < 1e-9);
+        assert!((limited.twist_angle(axis).take_degrees() - 45.0).abs() < 1e-9);
+        assert!(same_rotation(q.clamp_swing_twist(axis, Angle::Degrees(90.0), Angle::Degrees(-90.0), Angle::Degrees(90.0)), q));
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+        AngleF::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local up = vec3f(0, 1, 0)
+            local q = quatf_from_axis_angle(vec3f(1, 0, 0), degf(40)) * quatf_from_axis_angle(up, degf(-120))
+            local swing, twist = q:swing_twist(up)
+            assert(math.abs((swing * twist):dot(q) - 1) < 1e-5)
+            assert(math.abs(q:twist_angle(up):to_deg():take() + 120) < 1e-3)
+            local limited = q:clamp_swing_twist(up, degf(10), degf(-90), degf(90))
+            assert(math.abs(limited:twist_angle(up):to_deg():take() + 90) < 1e-3)
+            assert(math.abs(swing:clamp_angle(degf(10)):angle_to(quatf()):to_deg():take() - 10) < 1e-3)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_euler_operations() {
+        let deg = |yaw: f64, pitch: f64, roll: f64| EulerD::new(Angle::Degrees(yaw), Angle::Degrees(pitch), Angle::Degrees(roll));
+        let close = |a: EulerD, b: EulerD| {
+            let (a, b) = (a.take_degrees(), b.take_degrees());
+            (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs() < 1e-9
+        };
+
+        assert!(close(deg(10.0, 20.0, 30.0) + deg(1.0, 2.0, 3.0), deg(11.0, 22.0, 33.0)));
+        assert!(close(deg(10.0, 20.0, 30.0) - deg(1.0, 2.0, 3.0), deg(9.0, 18.0, 27.0)));
+        assert!(close(deg(10.0, 20.0, 30.0) * 2.0, deg(20.0, 40.0, 60.0)));
+        assert!(close(-deg(10.0, 20.0, 30.0), deg(-10.0, -20.0, -30.0)));
+        assert!(close(deg(370.0, -190.0, 180.0).wrapped(), deg(10.0, 170.0, 180.0)));
+        assert!(matches!(deg(370.0, 0.0, 0.0).wrapped().yaw, Angle::Degrees(_)));
+
+        let (a, b) = (deg(170.0, -10.0, 0.0), deg(-170.0, 30.0, 90.0));
+        assert!(close(EulerD::lerp(a, b, 0.5).wrapped(), deg(180.0, 10.0, 45.0)));
+        assert!(close(EulerD::lerp(a, b, 0.0), a) && close(EulerD::lerp(a, b, 1.0).wrapped(), b));
+        let v = EulerD::lerp(a, b, 0.25);
+        assert!((EulerD::inverse_lerp(a, b, v, |r| r.yaw.take()) - 0.25).abs() < 1e-9);
+        let (a, b) = (deg(0.0, 5.0, 0.0), deg(1.0, 5.0, 0.0));
+        let fractions = EulerD::inverse_lerp(a, b, deg(0.5, 5.0, 0.0), |r| r.yaw.take() + 10.0 * r.pitch.take());
+        assert!((fractions - 0.5).abs() < 1e-9);
+
+        let xyz = deg(10.0, 20.0, 30.0).with_order(RotationOrder::XYZ);
+        let expected = QuatD::from_euler_with_order(xyz, RotationOrder::XYZ);
+        assert!((xyz.to_quat().dot(expected).abs() - 1.0).abs() < 1e-12);
+        assert!((xyz.to_matrix() - Matrix3x3D::rotation(xyz, RotationOrder::XYZ)).frobenius_norm() < 1e-12);
+        assert_eq!(expected.to_euler(RotationOrder::XYZ).order, RotationOrder::XYZ);
+
+        let lua = Lua::new();
+        AngleF::load_lua(&lua).unwrap();
+        Angle::<f64>::load_lua(&lua).unwrap();
+        Vec3D::load_lua(&lua).unwrap();
+        QuatD::load_lua(&lua).unwrap();
+        Matrix3x3D::load_lua(&lua).unwrap();
+        EulerF::from_lua(&lua).unwrap();
+        EulerD::from_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local a = eulerd(deg(170), 0.5, nil, "XYZ")
+            assert(a:order() == "XYZ" and a:roll():take() == 0 and a:pitch():take() == 0.5)
+            local b = eulerd(deg(-170), deg(10), degf(20))
+            assert(b:order() == "ZYX")
+            local mid = eulerd_lerp(a, b, 0.5):wrapped()
+            assert(math.abs(math.abs(mid:yaw():to_deg():take()) - 180) < 1e-9)
+            assert((a + b - b) == a and 2 * a == a * 2 and -(-a) == a)
+            assert(eulerd(1, 2, 3):to_quat():to_euler():to_quat():dot(eulerd(1, 2, 3):to_quat()) > 0.999999)
+            assert(math.abs(a:to_mat3x3d():determinant() - 1) < 1e-9)
+            assert(mat3x3d_rotation(a) == a:to_mat3x3d())
+            assert(eulerf(degf(90)):with_order("XYZ"):order() == "XYZ")
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_angle_shortest_path() {
+        let close = |a: Angle<f64>, b: f64| (a.take() - b).abs() < 1e-9;
+        assert!(close(Angle::Degrees(-30.0).normalize_0_to_2pi(), 330.0));
+        assert!(close(Angle::Degrees(720.0).normalize_0_to_2pi(), 0.0));
+        assert_eq!(Angle::Radians(-1e-17).normalize_0_to_2pi(), Angle::Radians(0.0));
+        assert!(close(Angle::Degrees(190.0).normalize_signed(), -170.0));
+        assert!(close(Angle::Degrees(-180.0).normalize_signed(), 180.0));
+        assert!(close(Angle::Radians(3.0 * std::f64::consts::PI).normalize_signed(), std::f64::consts::PI));
+        assert!(close(Angle::Degrees(350.0).shortest_difference(Angle::Degrees(10.0)), 20.0));
+        assert!(close(Angle::Degrees(10.0).shortest_difference(Angle::Radians(350f64.to_radians())), -20.0));
+        assert!(close(Angle::lerp_shortest(Angle::Degrees(350.0), Angle::Degrees(10.0), 0.25), 355.0));
+        assert!(matches!(Angle::Degrees(-30.0).normalize_signed(), Angle::Degrees(_)));
+
+        let hsv = |h: f64| Color::HSV {
+            h: Angle::Degrees(h),
+            s: Percentage::from_100(50).to_color_component(),
+            v: Percentage::from_100(50).to_color_component(),
+        };
+        match Color::lerp(hsv(350.0), hsv(30.0), 0.5) {
+            Color::HSV { h, .. } => assert!(close(h, 10.0), "{h}"),
+            _ => unreachable!(),
+        }
+
+        let lua = Lua::new();
+        Angle::<f64>::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            assert(math.abs(deg(350):shortest_difference(deg(10)):take() - 20) < 1e-9)
+            assert(math.abs(deg(-190):normalize_signed():take() - 170) < 1e-9)
+            assert(math.abs(deg(-90):normalize_0_to_2pi():take() - 270) < 1e-9)
+            assert(math.abs(deg(350):lerp_shortest(deg(10), 0.5):normalize_0_to_2pi():take()) < 1e-9)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_angle_units() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(close(Angle::turns(0.25).take_degrees(), 90.0));
+        assert!(Angle::turns(0.5f64).is_radians() && Angle::gradians(100.0f64).is_degrees());
+        assert!(close(Angle::gradians(100.0).take_degrees(), 90.0));
+        assert!(close(Angle::Degrees(180.0).take_gradians(), 200.0));
+        assert!(close(Angle::Radians(std::f64::consts::PI).take_turns(), 0.5));
+        assert!(close(Angle::from_unit(90.0, AngleUnit::ArcMinutes).take_degrees(), 1.5));
+        assert!(close(Angle::Degrees(0.5).take_in(AngleUnit::ArcSeconds), 1800.0));
+        assert!(close(Angle::from_dms(12.0, 30.0, 15.0).take_degrees(), 12.504166666666666));
+        assert!(close(Angle::from_dms(0.0, -30.0, 0.0).take_degrees(), -0.5));
+
+        let dms = Angle::Degrees(12.504166666666666).to_dms();
+        assert_eq!(dms, Dms { negative: false, degrees: 12.0, minutes: 30.0, seconds: 15.0 });
+        assert_eq!(dms.to_string(), "12°30'15\"");
+        assert_eq!(Angle::Degrees(-0.25f64).to_dms().to_string(), "-0°15'0\"");
+        assert_eq!(format!("{:.2}", Angle::Degrees(1.0f64 / 7.0).to_dms()), "0°8'34.29\"");
+
+        let parsed: Angle<f64> = "12°30'15\"".parse().unwrap();
+        assert!(close(parsed.take_degrees(), 12.504166666666666) && parsed.is_degrees());
+        assert!(close("-12° 30′ 15″".parse::<Angle<f64>>().unwrap().take_degrees(), -12.504166666666666));
+        assert!(close("45.5".parse::<Angle<f64>>().unwrap().take_degrees(), 45.5));
+        assert!(close("30'".parse::<Angle<f64>>().unwrap().take_degrees(), 0.5));
+        for invalid in ["", "12'30°", "12°°", "abc", "12°x"] {
+            assert!(invalid.parse::<Angle<f64>>().is_err(), "{invalid}");
+        }
+
+        let lua = Lua::new();
+        AngleF::load_lua(&lua).unwrap();
+        Angle::<f64>::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            assert(math.abs(turn(0.25):to_deg():take() - 90) < 1e-9)
+            assert(math.abs(grad(50):take_in("deg") - 45) < 1e-9)
+            assert(math.abs(turnf(1):take_in("grad") - 400) < 1e-3)
+            assert(math.abs(gradf(100):take() - 90) < 1e-5)
+            assert(dms(12, 30, 15):dms() == "12°30'15\"")
+            assert(math.abs(dms("-1°30'"):take() + 1.5) < 1e-9)
+            assert(math.abs(dmsf(1, 30):take_in("arcmin") - 90) < 1e-3)
+            assert(not pcall(dms, "nope"))
+            assert(not pcall(function() return deg(1):take_in("parsecs") end))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_angle_inverse_trig() {
+        let close = |a: Angle<f64>, degrees: f64| (a.take_degrees() - degrees).abs() < 1e-9;
+        assert!(close(Angle::asin(0.5), 30.0) && close(Angle::acos(0.5), 60.0) && close(Angle::atan(1.0), 45.0));
+        assert!(close(Angle::atan2(1.0, -1.0), 135.0) && close(Angle::atan2(-1.0, -1.0), -135.0));
+        assert!(Angle::asin(0.5f64).is_radians());
+
+        assert!(close(Vec2D::from_array([0.0, 2.0]).angle(), 90.0));
+        assert!(close(Vec2D::from_array([-1.0, 0.0]).angle(), 180.0));
+        assert!(close(Vec2D::from_array([1.0, -1.0]).angle(), -45.0));
+
+        let (x, y) = (Vec3D::from_array([1.0, 0.0, 0.0]), Vec3D::from_array([1.0, 1.0, 0.0]));
+        assert!(close(Angle::from_vectors(x, y), 45.0) && close(Angle::from_vectors(x, -x), 180.0));
+        let nearly = Vec3D::from_array([1.0, 1e-9, 0.0]);
+        assert!((Angle::from_vectors(x, nearly).take_radians() - 1e-9).abs() < 1e-15);
+        assert!(Angle::from_vectors(x, x * 3.0).take() == 0.0);
+
+        let lua = Lua::new();
+        AngleF::load_lua(&lua).unwrap();
+        Angle::<f64>::load_lua(&lua).unwrap();
+        Vec2F::load_lua(&lua).unwrap();
+        Vec2D::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            assert(math.abs(asin(1):to_deg():take() - 90) < 1e-9)
+            assert(math.abs(acosf(0):to_deg():take() - 90) < 1e-4)
+            assert(math.abs(atan(1, -1):to_deg():take() - 135) < 1e-9)
+            assert(math.abs(atan(1):to_deg():take() - 45) < 1e-9)
+            assert(math.abs(atan2f(-1, 0):to_deg():take() + 90) < 1e-4)
+            assert(math.abs(vec2d(0, -3):polar_angle():to_deg():take() + 90) < 1e-9)
+            assert(math.abs(vec2f(1, 1):angle(vec2f(0, 1)):to_deg():take() - 45) < 1e-4)
+            assert(math.abs((degf(90) / 2):take() - 45) < 1e-4)
+            assert(math.abs((degf(90) / degf(2)):take() - 45) < 1e-4)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_angle_lua_surface() {
+        assert!(Angle::Radians(1.0f64) > Angle::Degrees(45.0) && Angle::Degrees(45.0f64) < Angle::Radians(1.0));
+        assert!((Angle::Degrees(90.0f64).sin() - 1.0).abs() < 1e-12 && Angle::Degrees(180.0f64).cos() < -0.999);
+        assert_eq!(AngleF::Degrees(1.5).to_angled(), AngleD::Degrees(1.5));
+
+        let lua = Lua::new();
+        AngleF::load_lua(&lua).unwrap();
+        Angle::<f64>::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local function near(a, b) return math.abs(a - b) < 1e-4 end
+            assert(near((deg(90) - deg(30)):take(), 60) and near((degf(90) - degf(30)):take(), 60))
+            assert(near((deg(90) / deg(2)):take(), 45) and near((degf(90) / 3):take(), 30))
+            assert(near((deg(10) + 5):take(), 15) and near((5 + degf(10)):take(), 15))
+            assert(near((100 - deg(10)):take(), 90) and near((deg(100) - 10):take(), 90))
+            assert(near((2 * degf(10)):take(), 20) and near((deg(10) * 2):take(), 20))
+            assert(near((90 / deg(45)):take(), 2))
+            assert(near((-degf(30)):take(), -30) and near((rad(math.pi) + deg(180)):take(), 2 * math.pi))
+            assert(deg(180) == rad(math.pi) and degf(10) ~= degf(11))
+            assert(deg(10) < deg(20) and not (deg(20) < deg(10)) and deg(20) <= deg(20) and deg(30) > rad(0.5))
+            assert(degf(1) < 2 and 0.5 <= radf(0.5))
+            assert(near(deg(30):sin(), 0.5) and near(radf(0):cos(), 1) and near(deg(45):tan(), 1))
+            assert(near(deg(-90):normalize():take(), 270) and near(deg(450):normalize():take(), 90))
+            local d = degf(12.5):to_angled()
+            assert(d == deg(12.5) and d:to_anglef() == degf(12.5))
+            assert(not pcall(function() return deg(1) + degf(1) end))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_scalar_helpers() {
+        fn generic<S: Scalar>(x: S) -> [S; 6] {
+            [
+                x.atan2(S::ONE),
+                x.exp().ln(),
+                x.log(S::TWO),
+                x.min(S::ONE).max(S::ZERO),
+                x.mul_add(S::TWO, S::ONE),
+                S::THREE.hypot(S::FOUR),
+            ]
+        }
+        assert_eq!(generic(8.0f64), [8f64.atan2(1.0), 8f64.exp().ln(), 3.0, 1.0, 17.0, 5.0]);
+        assert_eq!(generic(-2.0f32)[3], 0.0);
+        assert_eq!(Scalar::round(2.5f64), 3.0);
+        assert_eq!(Scalar::round(-2.5f32), -3.0);
+        assert!((Scalar::fract(-1.25f64) + 0.25).abs() < 1e-12);
+        assert_eq!((Scalar::signum(-3.0f64), Scalar::copysign(2.0f32, -0.0)), (-1.0, -2.0));
+        assert_eq!(<f32 as Scalar>::EPSILON, f32::EPSILON);
+        assert!(Scalar::is_nan(f64::NAN) && !Scalar::is_finite(<f64 as Scalar>::INFINITY) && Scalar::is_finite(1.0f32));
+
+        let lua = Lua::new();
+        scalar::load_mathx(&lua).unwrap();
+        lua.load(
+            r#"
+            assert(math.abs(mathx.atan2(1, -1) - 3 * math.pi / 4) < 1e-12)
+            assert(math.abs(mathx.ln(mathx.exp(2)) - 2) < 1e-12 and math.abs(mathx.log(1000, 10) - 3) < 1e-12)
+            assert(mathx.min(1, 2) == 1 and mathx.max(1, 2) == 2)
+            assert(mathx.round(-0.5) == -1 and math.abs(mathx.fract(3.75) - 0.75) < 1e-12)
+            assert(mathx.signum(-7) == -1 and mathx.copysign(3, -1) == -3)
+            assert(mathx.mul_add(2, 3, 4) == 10 and mathx.hypot(5, 12) == 13)
+            assert(mathx.is_nan(0 / 0) and not mathx.is_finite(mathx.infinity) and mathx.is_finite(1))
+            assert(1 + mathx.epsilon ~= 1 and 1 + mathx.epsilon / 2 == 1)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let q = Q32_32::from_f64;
+        for i in -300..300 {
+            let x = i as f64 * 0.0213;
+            assert!((q(x).sine().to_f64() - x.sin()).abs() < 1e-8, "sin {x}");
+            assert!((q(x).cosine().to_f64() - x.cos()).abs() < 1e-8, "cos {x}");
+            assert!((q(x).atan2(q(-0.4)).to_f64() - x.atan2(-0.4)).abs() < 1e-8, "atan2 {x}");
+            assert!((q(x).exp().to_f64() / x.exp() - 1.0).abs() < 1e-7, "exp {x}");
+            assert!((q(x.abs() + 0.01).ln().to_f64() - (x.abs() + 0.01).ln()).abs() < 1e-7, "ln {x}");
+            assert!((q(x.abs()).square_root2().to_f64() - x.abs().sqrt()).abs() < 1e-9, "sqrt {x}");
+        }
+        assert!((q(2.0).power_f(q(0.5)).to_f64() - 2f64.sqrt()).abs() < 1e-8);
+        assert_eq!(q(-2.0).power_f(q(3.0)), q(-8.0));
+        assert!((q(30.0).rads().sine().inverse_sine().degs().to_f64() - 30.0).abs() < 1e-6);
+
+        let x = Q16_16::from_f64;
+        assert_eq!(x(1.5) * x(2.25), x(3.375));
+        assert_eq!(x(-7.25) / x(2.0), x(-3.625));
+        assert_eq!((x(-2.5).round(), x(2.5).round(), x(-2.5).s_floor(), x(-2.5).s_ceil()), (x(-3.0), x(3.0), x(-3.0), x(-2.0)));
+        assert_eq!((x(-1.25).fract(), x(-3.0).signum(), x(2.0).copysign(x(-1.0))), (x(-0.25), x(-1.0), x(-2.0)));
+        assert_eq!(x(2.0).mul_add(x(3.0), x(0.5)), x(6.5));
+        assert_eq!(x(7.5) % x(2.0), x(1.5));
+        assert_eq!(Q16_16::INFINITY + Q16_16::ONE, Q16_16::INFINITY);
+        assert_eq!(Q16_16::ONE / Q16_16::ZERO, Q16_16::INFINITY);
+        assert!(!(-Q16_16::INFINITY).is_finite() && Q16_16::EPSILON.to_bits() == 1);
+        assert_eq!((Q16_16::PI.to_bits(), x(1.0).sine().to_bits(), x(0.5).exp().to_bits()), (205887, 55147, 108051));
+
+        let v = Vec3::from_array([x(1.0), x(2.0), x(2.0)]);
+        assert_eq!(v.magnitude(), x(3.0));
+        let world = Vec3::from_array([x(3000.0), x(-4000.0), x(12000.0)]);
+        assert_eq!((world.magnitude(), Vec3::from_array([x(200.0), x(0.0), x(0.0)]).magnitude()), (x(13000.0), x(200.0)));
+        assert_eq!(world.dot_product(&Vec3::from_array([x(4000.0), x(3000.0), x(0.0)])), x(0.0));
+        assert!((world.normalized()[2].to_f64() - 12.0 / 13.0).abs() < 1e-4);
+        assert_eq!(v.cross(&Vec3::from_array([x(0.0), x(0.0), x(1.0)])), Vec3::from_array([x(2.0), x(-1.0), x(0.0)]));
+        let qx = Quaternion::from_axis_angle(Vec3::from_array([q(0.0), q(0.0), q(1.0)]), Angle::Degrees(q(90.0)));
+        let qd = QuatD::from_axis_angle(Vec3D::from_array([0.0, 0.0, 1.0]), Angle::Degrees(90.0));
+        let (rx, rd) = (qx.rotate(Vec3::from_array([q(1.0), q(2.0), q(3.0)])), qd.rotate(Vec3D::from_array([1.0, 2.0, 3.0])));
+        assert!((0..3).all(|i| (rx[i].to_f64() - rd[i]).abs() < 1e-7));
+        let m: Matrix3x3<Q16_16> = Matrix3x3::z_rotation(Angle::Degrees(x(30.0)));
+        assert!((m.determinant().to_f64() - 1.0).abs() < 1e-3);
+        assert!((Angle::Radians(Q16_16::PI).take_degrees().to_f64() - 180.0).abs() < 1e-3);
+
+        let lua = Lua::new();
+        Vec3X::load_lua(&lua).unwrap();
+        QuatX::load_lua(&lua).unwrap();
+        Matrix3x3X::load_lua(&lua).unwrap();
+        EulerX::from_lua(&lua).unwrap();
+        AngleX::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local v = vec3x(1, 2, 2)
+            assert(#v == 3 and v:dot(vec3x(1, 1, 1)) == 5 and (v + v):magnitude() == 6)
+            assert(vec3x(200, 0, 0):magnitude() == 200 and #vec3x(3000, 4000, 0) == 5000)
+            local q = quatx_from_axis_angle(vec3x(0, 0, 1), degx(90))
+            local r = q * vec3x(1, 0, 0)
+            assert(math.abs(r:dot(vec3x(0, 1, 0)) - 1) < 1e-3)
+            assert(math.abs(q:to_mat3x3x():determinant() - 1) < 1e-3)
+            local z = mat3x3x_z_rotation(radx(math.pi / 2))
+            assert(math.abs(math.abs(z:row(0):dot(vec3x(0, 1, 0))) - 1) < 1e-3)
+            local e = eulerx(degx(30), 0, 0)
+            assert(math.abs(e:yaw():to_deg():take() - 30) < 1e-3)
+            assert(math.abs(math.abs(e:to_quat():dot(quatx_from_axis_angle(vec3x(0, 0, 1), degx(30)))) - 1) < 1e-3)
+            assert(math.abs((degx(180) - radx(math.pi)):take()) < 1e-2)
+            assert(math.abs(turnx(0.25):to_deg():take() - 90) < 1e-2 and math.abs(gradx(100):to_deg():take() - 90) < 1e-2)
+            assert(math.abs(dmsx(12, 30):to_deg():take() - 12.5) < 1e-3 and math.abs(dmsx("12°30'"):take() - 12.5) < 1e-3)
+            assert(math.abs(asinx(1):to_deg():take() - 90) < 1e-2 and math.abs(acosx(0):to_deg():take() - 90) < 1e-2)
+            assert(math.abs(atanx(1):to_deg():take() - 45) < 1e-2 and math.abs(atanx(1, -1):to_deg():take() - 135) < 1e-2)
+            assert(math.abs(atan2x(-1, 0):to_deg():take() + 90) < 1e-2)
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_rotation_representations() {
+        let v = Vec3D::from_array([1.0, -2.0, 0.5]);
+        let close = |a: Vec3D, b: Vec3D| (a - b).magnitude() < 1e-9;
+        let axis_angle = AxisAngleD::new(Vec3D::from_array([0.0, 3.0, 4.0]), Angle::Degrees(75.0));
+        let expected = axis_angle.rotate(v);
+
+        let q: QuatD = axis_angle.convert();
+        let m: Matrix3x3D = q.convert();
+        let e: EulerD = m.convert();
+        let back: AxisAngleD = e.convert();
+        for rotated in [q.rotate(v), Rotation::rotate(m, v), Rotation::rotate(e, v), back.rotate(v)] {
+            assert!(close(rotated, expected));
+        }
+        assert!(close(m.transform(v), expected));
+        assert!((back.angle.take_degrees() - 75.0).abs() < 1e-9 && close(back.axis, axis_angle.axis));
+
+        let other = QuatD::from_axis_angle(Vec3D::RIGHT, Angle::Degrees(-40.0));
+        assert!(close(Rotation::then(e, other).rotate(v), other * expected));
+        assert!(close(Rotation::then(m, axis_angle).rotate(v), axis_angle.rotate(expected)));
+        assert!(close(axis_angle.invert().rotate(expected), v));
+        assert!(close(Rotation::rotate(Rotation::invert(m), expected), v));
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+        AngleF::load_lua(&lua).unwrap();
+        Matrix3x3F::load_lua(&lua).unwrap();
+        EulerF::from_lua(&lua).unwrap();
+        AxisAngleF::load_lua(&lua).unwrap();
+        AnyRotation::<f32>::load_lua(&lua).unwrap();
+        Vec3D::load_lua(&lua).unwrap();
+        QuatD::load_lua(&lua).unwrap();
+        Angle::<f64>::load_lua(&lua).unwrap();
+        Matrix3x3D::load_lua(&lua).unwrap();
+        EulerD::from_lua(&lua).unwrap();
+        AxisAngleD::load_lua(&lua).unwrap();
+        AnyRotation::<f64>::load_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local v = vec3f(1, 2, 3)
+            local aa = axis_anglef(vec3f(0, 0, 2), degf(90))
+            local expected = rotf_rotate(aa, v)
+            assert((expected - vec3f(-2, 1, 3)):magnitude() < 1e-5)
+            for _, r in ipairs({aa, rotf_to_quat(aa), rotf_to_mat3x3(aa), rotf_to_euler(aa), rotf_to_euler(aa, "XYZ")}) do
+                assert((rotf_rotate(r, v) - expected):magnitude() < 1e-5)
+                assert((rotf_rotate(rotf_inverse(r), expected) - v):magnitude() < 1e-5)
+            end
+            local twice = rotf_then(rotf_to_mat3x3(aa), aa)
+            assert((rotf_rotate(twice, v) - vec3f(-1, -2, 3)):magnitude() < 1e-5 and twice:determinant() > 0.999)
+            assert(math.abs(rotf_to_axis_angle(rotf_to_quat(aa)):angle():to_deg():take() - 90) < 1e-3)
+            assert(not pcall(rotf_to_quat, v))
+
+            local ad = axis_angled(vec3d(0, 0, 2), deg(90))
+            local md = rotd_to_mat3x3(rotd_to_euler(rotd_to_quat(ad)))
+            assert((rotd_rotate(rotd_then(md, ad), vec3d(1, 2, 3)) - vec3d(-1, -2, 3)):magnitude() < 1e-12)
+            assert(math.abs(rotd_to_axis_angle(rotd_inverse(md)):angle():to_deg():take() - 90) < 1e-9)
+            assert(not pcall(rotd_to_quat, aa))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_euler_round_trip() {
+        let same_rotation = |a: QuatD, b: QuatD| (a.dot(b).abs() - 1.0).abs() < 1e-9;
+        let mut seed = 0x2545f491u64;
+        let mut random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % 20000) as f64 / 10000.0 - 1.0
+        };
+
+        let mut rotations = (0..200)
+            .map(|_| QuatD { w: random(), xyz: Vec3D::from_array([random(), random(), random()]) }.normalized())
+            .collect::<Vec<_>>();
+        for pitch in [90.0, -90.0] {
+            let locked = EulerD::new(Angle::Degrees(30.0), Angle::Degrees(pitch), Angle::Degrees(-50.0));
+            rotations.push(QuatD::from_euler(locked));
+            rotations.push(QuatD::from_euler_with_order(locked, RotationOrder::XYZ));
+        }
+
+        for order in [RotationOrder::ZYX, RotationOrder::XYZ] {
+            for &q in &rotations {
+                let euler = q.to_euler(order);
+                assert!(euler.pitch.take().abs() <= std::f64::consts::FRAC_PI_2 + 1e-9);
+                assert!(same_rotation(QuatD::from_euler_with_order(euler, order), q), "{order:?} {q:?}");
+
+                let m = q.to_rotation_3x3();
+                let back = Matrix3x3D::rotation(m.to_euler(order), order);
+                assert!((back - m).frobenius_norm() < 1e-9);
+            }
+        }
+
+        let locked = QuatD::from_euler(EulerD::new(Angle::Degrees(30.0), Angle::Degrees(90.0), Angle::Degrees(-50.0)));
+        let canonical = locked.to_euler(RotationOrder::ZYX);
+        assert!((canonical.yaw.take_degrees() - 80.0).abs() < 1e-4);
+        assert_eq!(canonical.roll.take(), 0.0);
+
+        let euler = EulerD::new(Angle::Degrees(10.0), Angle::Degrees(20.0), Angle::Degrees(30.0));
+        assert!(same_rotation(QuatD::from_euler(euler), QuatD::from_euler_with_order(euler, RotationOrder::ZYX)));
+        assert!((Matrix3x3D::rotation(euler, RotationOrder::ZYX) - QuatD::from_euler(euler).to_rotation_3x3()).frobenius_norm() < 1e-12);
+
+        let lua = Lua::new();
+        Vec3F::load_lua(&lua).unwrap();
+        Matrix3x3F::load_lua(&lua).unwrap();
+        QuatF::load_lua(&lua).unwrap();
+        AngleF::load_lua(&lua).unwrap();
+        EulerF::from_lua(&lua).unwrap();
+        lua.load(
+            r#"
+            local q = quatf_from_axis_angle(vec3f(0, 1, 0), degf(40))
+            local e = q:to_euler("XYZ")
+            local _, angle = e:to_quat():to_axis_angle()
+            assert(math.abs(angle:to_deg():take() - 40) < 1e-3)
+            local m = mat3x3f_rotation(eulerf(degf(10), degf(20), degf(30)), "XYZ")
+            local back = mat3x3f_rotation(m:to_euler("XYZ"), "XYZ")
+            assert((back - m):frobenius_norm() < 1e-5)
+            assert(tostring(q:to_euler()) == tostring(q:to_euler("zyx")))
+            "#,
+        )
+        .exec()
+        .unwrap();
+    }
+
     #[test]
     fn it_works() {
         let lua = Lua::new();
@@ -79,16 +1228,23 @@ mod tests {
         Vec3D::load_lua(&lua).unwrap();
 
         Vec4F::load_lua(&lua).unwrap();
+        Vec4D::load_lua(&lua).unwrap();
 
-        //Angle::load_lua(&lua).unwrap();
+        AngleF::load_lua(&lua).unwrap();
+        Angle::<f64>::load_lua(&lua).unwrap();
 
         Matrix2x2F::load_lua(&lua).unwrap();
         Matrix3x3F::load_lua(&lua).unwrap();
+        Matrix3x3D::load_lua(&lua).unwrap();
         Matrix4x4F::load_lua(&lua).unwrap();
+        Matrix4x4D::load_lua(&lua).unwrap();
+        DMatrixD::load_lua(&lua).unwrap();
+        DVectorD::load_lua(&lua).unwrap();
 
         EulerF::from_lua(&lua).unwrap();
 
         QuatF::load_lua(&lua).unwrap();
+        QuatD::load_lua(&lua).unwrap();
 
         let t = lua
             .load(
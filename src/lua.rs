@@ -0,0 +1,14 @@
+///Lua errors on an index past `len` instead of panicking inside the binding.
+pub(crate) fn check_bounds(index: usize, len: usize) -> rlua::Result<usize> {
+    crate::ternary!(
+        index < len => Ok(index) ;
+        Err(rlua::Error::RuntimeError(format!("index {index} out of bounds for length {len}")))
+    )
+}
+
+///The registered type name of `ud`, for error messages.
+pub(crate) fn type_name(ud: &rlua::AnyUserData) -> String {
+    ud.get_metatable()
+        .and_then(|mt| mt.get::<String>(rlua::MetaMethod::Type.name()))
+        .unwrap_or_else(|_| "userdata".to_string())
+}
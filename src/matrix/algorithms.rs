@@ -0,0 +1,131 @@
+//! Algorithms over row-major slices, shared by the fixed size [`Matrix`](crate::matrix::Matrix)
+//! and the heap backed [`DMatrix`](crate::matrix::dmatrix::DMatrix).
+
+use crate::scalar::Scalar;
+use std::cmp::Ordering;
+
+fn pivot_row<S: Scalar>(data: &[S], n: usize, col: usize) -> usize {
+    (col..n)
+        .max_by(|a, b| {
+            data[a * n + col]
+                .s_abs()
+                .partial_cmp(&data[b * n + col].s_abs())
+                .unwrap_or(Ordering::Equal)
+        })
+        .unwrap_or(col)
+}
+
+fn swap_rows<S: Scalar>(data: &mut [S], cols: usize, a: usize, b: usize) {
+    if a != b {
+        for col in 0..cols {
+            data.swap(a * cols + col, b * cols + col);
+        }
+    }
+}
+
+///Subtracts `factor` times row `source` from row `target`.
+fn eliminate<S: Scalar>(data: &mut [S], cols: usize, target: usize, source: usize, factor: S) {
+    for col in 0..cols {
+        let value = data[source * cols + col];
+        data[target * cols + col] -= value * factor;
+    }
+}
+
+///Determinant of the `n x n` matrix in `data` by Gaussian elimination. `data` is left in row echelon form.
+pub(crate) fn determinant<S: Scalar>(data: &mut [S], n: usize) -> S {
+    let mut det = S::ONE;
+
+    for col in 0..n {
+        let pivot = pivot_row(data, n, col);
+        if data[pivot * n + col] == S::ZERO {
+            return S::ZERO;
+        }
+        if pivot != col {
+            swap_rows(data, n, pivot, col);
+            det = -det;
+        }
+        det *= data[col * n + col];
+
+        for row in col + 1..n {
+            let factor = data[row * n + col] / data[col * n + col];
+            eliminate(data, n, row, col, factor);
+        }
+    }
+
+    det
+}
+
+///Gauss-Jordan elimination with partial pivoting, writing the inverse of `data` into `out`.
+///Returns `false` when the matrix is singular.
+pub(crate) fn invert<S: Scalar>(data: &mut [S], out: &mut [S], n: usize) -> bool {
+    for row in 0..n {
+        for col in 0..n {
+            out[row * n + col] = crate::ternary!(row == col => S::ONE ; S::ZERO);
+        }
+    }
+
+    for col in 0..n {
+        let pivot = pivot_row(data, n, col);
+        if data[pivot * n + col] == S::ZERO {
+            return false;
+        }
+        swap_rows(data, n, pivot, col);
+        swap_rows(out, n, pivot, col);
+
+        let scale = S::ONE / data[col * n + col];
+        for i in 0..n {
+            data[col * n + i] *= scale;
+            out[col * n + i] *= scale;
+        }
+
+        for row in 0..n {
+            if row != col {
+                let factor = data[row * n + col];
+                eliminate(data, n, row, col, factor);
+                eliminate(out, n, row, col, factor);
+            }
+        }
+    }
+
+    true
+}
+
+///`out = a * b` where `a` is `rows x inner` and `b` is `inner x cols`.
+pub(crate) fn multiply<S: Scalar>(a: &[S], b: &[S], out: &mut [S], rows: usize, inner: usize, cols: usize) {
+    for row in 0..rows {
+        for col in 0..cols {
+            out[row * cols + col] = (0..inner).map(|k| a[row * inner + k] * b[k * cols + col]).sum::<S>();
+        }
+    }
+}
+
+///Number of pivots larger than `tolerance` found by Gaussian elimination of the `rows x cols` matrix in `data`.
+pub(crate) fn rank<S: Scalar>(data: &mut [S], rows: usize, cols: usize, tolerance: S) -> usize {
+    let mut rank = 0;
+
+    for col in 0..cols {
+        if rank == rows {
+            break;
+        }
+        let pivot = (rank..rows)
+            .max_by(|a, b| {
+                data[a * cols + col]
+                    .s_abs()
+                    .partial_cmp(&data[b * cols + col].s_abs())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap_or(rank);
+        if data[pivot * cols + col].s_abs() <= tolerance {
+            continue;
+        }
+        swap_rows(data, cols, pivot, rank);
+
+        for row in rank + 1..rows {
+            let factor = data[row * cols + col] / data[rank * cols + col];
+            eliminate(data, cols, row, rank, factor);
+        }
+        rank += 1;
+    }
+
+    rank
+}
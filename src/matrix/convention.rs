@@ -0,0 +1,74 @@
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::vector::Vector;
+use std::ops::Mul;
+
+///How the matrices built by this crate are meant to be multiplied and uploaded.
+///
+///Storage itself is always `[row][col]`, so `as_ptr` always yields the rows one after another.
+///The convention decides which side vectors multiply from, and therefore whether that memory
+///reads as row-major or column-major to a graphics API.
+///
+///The default is [`MatrixConvention::RowMajor`]; enable the `column_major` cargo feature to switch every
+///constructor to [`MatrixConvention::ColumnMajor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixConvention {
+    ///Column vectors and post-multiplication (`M * v`), as written in most math texts.
+    ///Translation lives in the last column and `a * b` applies `b` first.
+    ///`as_ptr` yields row-major data, so OpenGL needs the transpose flag set when uploading.
+    RowMajor,
+    ///Row vectors and pre-multiplication (`v * M`), every constructor returns the transpose of its
+    ///[`MatrixConvention::RowMajor`] counterpart. Translation lives in the last row and `a * b` applies `a` first.
+    ///`as_ptr` yields OpenGL's native column-major layout and can be uploaded as-is.
+    ColumnMajor,
+}
+
+#[cfg(not(feature = "column_major"))]
+pub const CONVENTION: MatrixConvention = MatrixConvention::RowMajor;
+#[cfg(feature = "column_major")]
+pub const CONVENTION: MatrixConvention = MatrixConvention::ColumnMajor;
+
+impl MatrixConvention {
+    ///Whether vectors multiply from the left (`v * M`).
+    pub const fn is_pre_multiplication(self) -> bool {
+        match self {
+            MatrixConvention::RowMajor => false,
+            MatrixConvention::ColumnMajor => true,
+        }
+    }
+
+    ///Whether the data behind `as_ptr` has to be transposed for an API expecting column-major matrices (OpenGL, Vulkan).
+    pub const fn upload_transposed(self) -> bool {
+        !self.is_pre_multiplication()
+    }
+}
+
+impl<const N: usize, S: Scalar> Matrix<N, N, S> {
+    ///Converts a matrix written for column vectors into the crate's [`CONVENTION`].
+    ///The conversion is its own inverse, so it also brings a conventional matrix back to column-vector form.
+    pub fn conventional(self) -> Self {
+        match CONVENTION {
+            MatrixConvention::RowMajor => self,
+            MatrixConvention::ColumnMajor => self.transpose(),
+        }
+    }
+
+    ///Applies the matrix to `v` from whichever side [`CONVENTION`] multiplies vectors.
+    pub fn transform(&self, v: Vector<N, S>) -> Vector<N, S> {
+        match CONVENTION {
+            MatrixConvention::RowMajor => *self * v,
+            MatrixConvention::ColumnMajor => v * *self,
+        }
+    }
+
+    ///Composes two transforms so that `self` is applied first and `next` second, regardless of [`CONVENTION`].
+    pub fn then(self, next: Self) -> Self
+    where
+        Self: Mul<Output = Self>,
+    {
+        match CONVENTION {
+            MatrixConvention::RowMajor => next * self,
+            MatrixConvention::ColumnMajor => self * next,
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use crate::matrix::matrix2x2::Matrix2x2D;
+use crate::matrix::matrix2x3::Matrix2x3D;
+use crate::matrix::matrix3x2::Matrix3x2D;
+use crate::matrix::matrix3x3::Matrix3x3D;
+use crate::matrix::matrix3x4::Matrix3x4D;
+use crate::matrix::matrix4x3::Matrix4x3D;
+use crate::matrix::matrix4x4::Matrix4x4D;
+use crate::matrix::{algorithms, Matrix};
+use crate::scalar::Scalar;
+use crate::lua::check_bounds;
+use crate::vector::dvector::{lua_error, DVector, DVectorD};
+use crate::vector::Vector;
+use crate::{LuaAssociatedFunction, LuaObject};
+use mlua::FromLua;
+use rlua::{Function, Lua, UserData, UserDataMethods};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+
+pub type DMatrixD = DMatrix<f64>;
+
+///Two dynamically sized operands did not have compatible `(rows, cols)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionMismatch {
+    pub expected: (usize, usize),
+    pub found: (usize, usize),
+}
+
+impl Display for DimensionMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "dimension mismatch: expected {}x{}, found {}x{}",
+            self.expected.0, self.expected.1, self.found.0, self.found.1
+        )
+    }
+}
+
+impl std::error::Error for DimensionMismatch {}
+
+///Heap backed, row-major matrix whose size is only known at runtime.
+///It runs the same elimination and product code as [`Matrix`].
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct DMatrix<S: Scalar> {
+    rows: usize,
+    cols: usize,
+    data: Vec<S>,
+}
+
+impl<S: Scalar> DMatrix<S> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![S::ZERO; rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut out = Self::zeros(n, n);
+        for i in 0..n {
+            out[i][i] = S::ONE;
+        }
+        out
+    }
+
+    ///`None` when `data` does not hold exactly `rows * cols` elements.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<S>) -> Option<Self> {
+        match data.len() == rows * cols {
+            true => Some(Self { rows, cols, data }),
+            false => None,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn as_slice(&self) -> &[S] {
+        &self.data
+    }
+
+    pub fn row(&self, row: usize) -> DVector<S> {
+        DVector::from_vec(self[row].to_vec())
+    }
+
+    pub fn col(&self, col: usize) -> DVector<S> {
+        DVector::from_vec((0..self.rows).map(|row| self[row][col]).collect())
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut out = Self::zeros(self.cols, self.rows);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                out[col][row] = self[row][col];
+            }
+        }
+        out
+    }
+
+    fn check_dimensions(&self, other: &Self) -> Result<(), DimensionMismatch> {
+        match self.dimensions() == other.dimensions() {
+            true => Ok(()),
+            false => Err(DimensionMismatch {
+                expected: self.dimensions(),
+                found: other.dimensions(),
+            }),
+        }
+    }
+
+    fn check_square(&self) -> Result<(), DimensionMismatch> {
+        match self.is_square() {
+            true => Ok(()),
+            false => Err(DimensionMismatch {
+                expected: (self.rows, self.rows),
+                found: self.dimensions(),
+            }),
+        }
+    }
+
+    pub fn determinant(&self) -> Result<S, DimensionMismatch> {
+        self.check_square()?;
+        Ok(algorithms::determinant(&mut self.data.clone(), self.rows))
+    }
+
+    ///`Ok(None)` when the matrix is square but singular.
+    pub fn inverse(&self) -> Result<Option<Self>, DimensionMismatch> {
+        self.check_square()?;
+        let mut out = Self::zeros(self.rows, self.cols);
+        Ok(match algorithms::invert(&mut self.data.clone(), &mut out.data, self.rows) {
+            true => Some(out),
+            false => None,
+        })
+    }
+
+    ///Numeric rank, see [`Matrix::rank`].
+    pub fn rank(&self) -> usize {
+        let norm_inf = (0..self.rows)
+            .map(|row| self[row].iter().map(|s| s.s_abs()).sum::<S>())
+            .fold(S::ZERO, |a, b| crate::ternary!(b > a => b ; a));
+        let tolerance = S::from_f64(self.rows.max(self.cols) as f64) * S::EPSILON * norm_inf;
+        algorithms::rank(&mut self.data.clone(), self.rows, self.cols, tolerance)
+    }
+
+    pub fn try_add(&self, rhs: &Self) -> Result<Self, DimensionMismatch> {
+        self.check_dimensions(rhs)?;
+        Ok(self.zip_map(rhs, |a, b| a + b))
+    }
+
+    pub fn try_sub(&self, rhs: &Self) -> Result<Self, DimensionMismatch> {
+        self.check_dimensions(rhs)?;
+        Ok(self.zip_map(rhs, |a, b| a - b))
+    }
+
+    pub fn try_mul(&self, rhs: &Self) -> Result<Self, DimensionMismatch> {
+        if self.cols != rhs.rows {
+            return Err(DimensionMismatch {
+                expected: (self.cols, rhs.cols),
+                found: rhs.dimensions(),
+            });
+        }
+        let mut out = Self::zeros(self.rows, rhs.cols);
+        algorithms::multiply(&self.data, &rhs.data, &mut out.data, self.rows, self.cols, rhs.cols);
+        Ok(out)
+    }
+
+    ///Column vector product `M * v`.
+    pub fn try_mul_vector(&self, rhs: &DVector<S>) -> Result<DVector<S>, DimensionMismatch> {
+        if self.cols != rhs.len() {
+            return Err(DimensionMismatch {
+                expected: (self.cols, 1),
+                found: (rhs.len(), 1),
+            });
+        }
+        let mut out = vec![S::ZERO; self.rows];
+        algorithms::multiply(&self.data, rhs.as_slice(), &mut out, self.rows, self.cols, 1);
+        Ok(DVector::from_vec(out))
+    }
+
+    fn map<F: Fn(S) -> S>(&self, f: F) -> Self {
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|s| f(*s)).collect(),
+        }
+    }
+
+    fn zip_map<F: Fn(S, S) -> S>(&self, rhs: &Self, f: F) -> Self {
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().zip(&rhs.data).map(|(a, b)| f(*a, *b)).collect(),
+        }
+    }
+}
+
+///Rows as slices, so elements read `m[row][col]` like on [`Matrix`].
+impl<S: Scalar> Index<usize> for DMatrix<S> {
+    type Output = [S];
+
+    fn index(&self, row: usize) -> &Self::Output {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+}
+
+impl<S: Scalar> IndexMut<usize> for DMatrix<S> {
+    fn index_mut(&mut self, row: usize) -> &mut Self::Output {
+        &mut self.data[row * self.cols..(row + 1) * self.cols]
+    }
+}
+
+impl<S: Scalar> Display for DMatrix<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let longest = self.data.iter().map(|s| s.to_string().len()).max().unwrap_or(0);
+        let center = (0..self.rows)
+            .map(|r| {
+                let center = self[r]
+                    .iter()
+                    .map(|s| {
+                        let a = s.to_string();
+                        let d = longest - a.len() + 1;
+                        format!("{:>d$}", a)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                let first = crate::ternary!(
+                    r == 0 => "⎡" ; crate::ternary!(r == self.rows - 1 => "⎣" ; "⎥")
+                );
+                let last = crate::ternary!(
+                    r == 0 => "⎤" ; crate::ternary!(r == self.rows - 1 => "⎦" ; "⎥")
+                );
+                format!("{first}{center}{last}")
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        f.write_fmt(format_args!("{center}"))
+    }
+}
+
+///Panics when the dimensions differ, see [`DMatrix::try_add`].
+impl<S: Scalar> Add<Self> for DMatrix<S> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.try_add(&rhs).unwrap()
+    }
+}
+
+///Panics when the dimensions differ, see [`DMatrix::try_sub`].
+impl<S: Scalar> Sub<Self> for DMatrix<S> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.try_sub(&rhs).unwrap()
+    }
+}
+
+///Panics when the inner dimensions differ, see [`DMatrix::try_mul`].
+impl<S: Scalar> Mul<Self> for DMatrix<S> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.try_mul(&rhs).unwrap()
+    }
+}
+
+///Panics when the inner dimensions differ, see [`DMatrix::try_mul_vector`].
+impl<S: Scalar> Mul<DVector<S>> for DMatrix<S> {
+    type Output = DVector<S>;
+
+    fn mul(self, rhs: DVector<S>) -> Self::Output {
+        self.try_mul_vector(&rhs).unwrap()
+    }
+}
+
+impl<S: Scalar> Mul<S> for DMatrix<S> {
+    type Output = Self;
+
+    fn mul(self, rhs: S) -> Self::Output {
+        self.map(|s| s * rhs)
+    }
+}
+
+impl<S: Scalar> Div<S> for DMatrix<S> {
+    type Output = Self;
+
+    fn div(self, rhs: S) -> Self::Output {
+        self.map(|s| s / rhs)
+    }
+}
+
+impl<S: Scalar> Neg for DMatrix<S> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|s| -s)
+    }
+}
+
+impl<const R: usize, const C: usize, S: Scalar> From<Matrix<R, C, S>> for DMatrix<S> {
+    fn from(m: Matrix<R, C, S>) -> Self {
+        Self {
+            rows: R,
+            cols: C,
+            data: m.as_slice().to_vec(),
+        }
+    }
+}
+
+impl<const R: usize, const C: usize, S: Scalar> TryFrom<DMatrix<S>> for Matrix<R, C, S> {
+    type Error = DimensionMismatch;
+
+    fn try_from(m: DMatrix<S>) -> Result<Self, Self::Error> {
+        if m.dimensions() != (R, C) {
+            return Err(DimensionMismatch {
+                expected: (R, C),
+                found: m.dimensions(),
+            });
+        }
+        let mut out = Matrix::ZERO;
+        out.as_mut_slice().copy_from_slice(&m.data);
+        Ok(out)
+    }
+}
+
+impl<const L: usize, S: Scalar> From<Vector<L, S>> for DMatrix<S> {
+    ///A single column.
+    fn from(v: Vector<L, S>) -> Self {
+        Self {
+            rows: L,
+            cols: 1,
+            data: DVector::from(v).as_slice().to_vec(),
+        }
+    }
+}
+
+///Tries every fixed size `f64` matrix type in turn, in the style of the `lua_matrix!` `Mul` dispatch.
+macro_rules! fixed_matrices {
+    ($m:ident => $($t:ty),*) => {
+        $(
+            if let Ok(m) = $m.borrow::<$t>() {
+                return Ok(DMatrixD::from(*m));
+            }
+        )*
+    };
+    ($this:ident, $lua:ident => $($t:ty),*) => {
+        $(
+            if let Ok(m) = <$t>::try_from($this.clone()) {
+                return rlua::IntoLua::into_lua(m, $lua);
+            }
+        )*
+    };
+}
+
+impl UserData for DMatrixD {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(rlua::MetaMethod::ToString, |_, this, _: ()| Ok(this.to_string()));
+        methods.add_meta_method(rlua::MetaMethod::Index, |_, this, index: usize| {
+            Ok(this.row(check_bounds(index, this.rows)?))
+        });
+        methods.add_method("get", |_, this, (c, r): (usize, usize)| {
+            Ok(this[check_bounds(r, this.rows)?][check_bounds(c, this.cols)?])
+        });
+        methods.add_method_mut("set", |_, this, (c, r, v): (usize, usize, f64)| {
+            let (r, c) = (check_bounds(r, this.rows)?, check_bounds(c, this.cols)?);
+            this[r][c] = v;
+            Ok(())
+        });
+
+        methods.add_method("rows", |_, this, _: ()| Ok(this.rows()));
+        methods.add_method("cols", |_, this, _: ()| Ok(this.cols()));
+        methods.add_method("row", |_, this, row: usize| Ok(this.row(check_bounds(row, this.rows)?)));
+        methods.add_method("col", |_, this, col: usize| Ok(this.col(check_bounds(col, this.cols)?)));
+        methods.add_method("transpose", |_, this, _: ()| Ok(this.transpose()));
+        methods.add_method("determinant", |_, this, _: ()| this.determinant().map_err(lua_error));
+        methods.add_method("inverse", |_, this, _: ()| this.inverse().map_err(lua_error));
+        methods.add_method("rank", |_, this, _: ()| Ok(this.rank()));
+        methods.add_method("to_fixed", |lua, this, _: ()| {
+            fixed_matrices!(this, lua => Matrix2x2D, Matrix2x3D, Matrix3x2D, Matrix3x3D, Matrix3x4D, Matrix4x3D, Matrix4x4D);
+            Err(rlua::Error::RuntimeError(format!("no fixed size matrix of {}x{}", this.rows, this.cols)))
+        });
+
+        methods.add_meta_method(rlua::MetaMethod::Eq, |_, this, b: Self| Ok(*this == b));
+        methods.add_meta_method(rlua::MetaMethod::Unm, |_, this, _: ()| Ok(-this.clone()));
+        methods.add_meta_method(rlua::MetaMethod::Add, |_, this, b: Self| this.try_add(&b).map_err(lua_error));
+        methods.add_meta_method(rlua::MetaMethod::Sub, |_, this, b: Self| this.try_sub(&b).map_err(lua_error));
+        methods.add_meta_function(rlua::MetaMethod::Mul, |lua, (a, b): (rlua::Value, rlua::Value)| {
+            match (a, b) {
+                (rlua::Value::UserData(a), rlua::Value::UserData(b)) => {
+                    let a = a.borrow::<Self>()?;
+                    if let Ok(b) = b.borrow::<Self>() {
+                        return rlua::IntoLua::into_lua(a.try_mul(&b).map_err(lua_error)?, lua);
+                    }
+                    let b = b.borrow::<DVectorD>()?;
+                    rlua::IntoLua::into_lua(a.try_mul_vector(&b).map_err(lua_error)?, lua)
+                }
+                (rlua::Value::UserData(m), s) | (s, rlua::Value::UserData(m)) => {
+                    rlua::IntoLua::into_lua(m.borrow::<Self>()?.clone() * f64::from_lua(s, lua)?, lua)
+                }
+                (a, b) => Err(rlua::Error::RuntimeError(format!(
+                    "cannot multiply {} by {}", a.type_name(), b.type_name()
+                ))),
+            }
+        });
+        methods.add_meta_function(rlua::MetaMethod::Div, |_, (this, s): (Self, f64)| Ok(this / s));
+    }
+}
+
+impl LuaObject for DMatrixD {
+    ///Rows, columns and an optional row-major table of elements.
+    type Args = (usize, usize, Option<Vec<f64>>);
+    const CONSTRUCTOR_NAME: &'static str = "matd";
+
+    fn create_constructor(lua: &Lua) -> rlua::Result<Function> {
+        lua.create_function(|_, (rows, cols, data): Self::Args| match data {
+            Some(data) => Self::from_vec(rows, cols, data).ok_or_else(|| {
+                rlua::Error::RuntimeError(format!("matd expects {} elements", rows * cols))
+            }),
+            None => Ok(Self::zeros(rows, cols)),
+        })
+    }
+
+    fn associated_functions(lua: &Lua) -> rlua::Result<Vec<LuaAssociatedFunction>> {
+        Ok(vec![
+            LuaAssociatedFunction {
+                name: "matd_identity",
+                function: lua.create_function(|_, n: usize| Ok(Self::identity(n)))?,
+            },
+            LuaAssociatedFunction {
+                name: "matd_from",
+                function: lua.create_function(|_, m: rlua::AnyUserData| {
+                    fixed_matrices!(m => Matrix2x2D, Matrix2x3D, Matrix3x2D, Matrix3x3D, Matrix3x4D, Matrix4x3D, Matrix4x4D);
+                    Err(rlua::Error::RuntimeError("matd_from expects a fixed size f64 matrix".to_string()))
+                })?,
+            },
+        ])
+    }
+}
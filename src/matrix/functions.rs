@@ -0,0 +1,210 @@
+//! Norms, products and analytic functions of matrices.
+
+use crate::matrix::{algorithms, Matrix};
+use crate::scalar::Scalar;
+use crate::vector::Vector;
+
+///Upper bound for the iterative methods below. They converge long before on well conditioned input.
+const MAX_ITERATIONS: usize = 100;
+///Enough halvings to bring the largest finite `f64` norm below one half.
+const MAX_SQUARINGS: usize = 1100;
+
+impl<const R: usize, const C: usize, S: Scalar> Matrix<R, C, S> {
+    ///Square root of the sum of all squared elements.
+    pub fn frobenius_norm(&self) -> S {
+        self.as_slice().iter().map(|s| s.squared()).sum::<S>().square_root2()
+    }
+
+    ///Largest absolute column sum.
+    pub fn norm_1(&self) -> S {
+        (0..C)
+            .map(|col| (0..R).map(|row| self[row][col].s_abs()).sum::<S>())
+            .fold(S::ZERO, |a, b| crate::ternary!(b > a => b ; a))
+    }
+
+    ///Largest absolute row sum.
+    pub fn norm_inf(&self) -> S {
+        (0..R)
+            .map(|row| (0..C).map(|col| self[row][col].s_abs()).sum::<S>())
+            .fold(S::ZERO, |a, b| crate::ternary!(b > a => b ; a))
+    }
+
+    ///Largest singular value, found by power iteration on `Aᵀ·A`.
+    pub fn spectral_norm(&self) -> S {
+        let gram = self.transpose() * *self;
+        let mut v = Vector::<C, S>::ONE;
+        //Uneven start so it is not orthogonal to the dominant eigenvector of symmetric input.
+        for i in 0..C {
+            v[i] = S::ONE / S::from_f64((i + 1) as f64);
+        }
+
+        let mut lambda = S::ZERO;
+        for _ in 0..MAX_ITERATIONS {
+            let next = gram * v;
+            let magnitude = next.magnitude();
+            if magnitude == S::ZERO {
+                return S::ZERO;
+            }
+            v = next * (S::ONE / magnitude);
+            if magnitude == lambda {
+                break;
+            }
+            lambda = magnitude;
+        }
+
+        lambda.square_root2()
+    }
+
+    ///Number of linearly independent rows, counting pivots above `max(R, C) · ε · ‖A‖∞`.
+    pub fn rank(&self) -> usize {
+        let tolerance = S::from_f64(R.max(C) as f64) * S::EPSILON * self.norm_inf();
+        self.rank_with_tolerance(tolerance)
+    }
+
+    pub fn rank_with_tolerance(&self, tolerance: S) -> usize {
+        let mut data = *self;
+        algorithms::rank(data.as_mut_slice(), R, C, tolerance)
+    }
+
+    ///Element-wise product.
+    pub fn hadamard(&self, rhs: &Self) -> Self {
+        let mut out = *self;
+        for (a, b) in out.as_mut_slice().iter_mut().zip(rhs.as_slice()) {
+            *a *= *b;
+        }
+        out
+    }
+
+    ///Block matrix where block `(i, j)` is `self[i][j] * rhs`.
+    pub fn kronecker<const R2: usize, const C2: usize>(
+        &self,
+        rhs: &Matrix<R2, C2, S>,
+    ) -> Matrix<{ R * R2 }, { C * C2 }, S>
+    where
+        [(); R * R2]:,
+        [(); C * C2]:,
+    {
+        let mut out = Matrix::ZERO;
+        for row in 0..R * R2 {
+            for col in 0..C * C2 {
+                out[row][col] = self[row / R2][col / C2] * rhs[row % R2][col % C2];
+            }
+        }
+        out
+    }
+}
+
+impl<const N: usize, S: Scalar> Matrix<N, N, S> {
+    fn identity_n() -> Self {
+        let mut out = Self::ZERO;
+        for i in 0..N {
+            out[i][i] = S::ONE;
+        }
+        out
+    }
+
+    ///`self` multiplied with itself `n` times by repeated squaring. Negative powers use the inverse
+    ///and are `None` when the matrix is singular.
+    pub fn power(&self, n: i32) -> Option<Self> {
+        let mut base = crate::ternary!(n < 0 => self.inverse()? ; *self);
+        let mut n = n.unsigned_abs();
+        let mut out = Self::identity_n();
+
+        while n > 0 {
+            if n & 1 == 1 {
+                out = out * base;
+            }
+            base = base * base;
+            n >>= 1;
+        }
+
+        Some(out)
+    }
+
+    ///Matrix exponential `e^A` by scaling and squaring of the Taylor series. Every element is NaN
+    ///when `self` has a non-finite element.
+    pub fn exp(&self) -> Self {
+        if !self.norm_1().is_finite() {
+            return Self::from_array([Vector::from_array([S::from_f64(f64::NAN); N]); N]);
+        }
+
+        let mut scaled = *self;
+        let mut squarings = 0;
+        while scaled.norm_1() > S::ONE / S::TWO && squarings < MAX_SQUARINGS {
+            scaled = scaled / S::TWO;
+            squarings += 1;
+        }
+
+        let mut out = Self::identity_n();
+        let mut term = Self::identity_n();
+        for k in 1..MAX_ITERATIONS {
+            term = term * scaled / S::from_f64(k as f64);
+            let next = out + term;
+            if next == out {
+                break;
+            }
+            out = next;
+        }
+
+        for _ in 0..squarings {
+            out = out * out;
+        }
+        out
+    }
+
+    ///Principal square root by the Denman–Beavers iteration, `None` when it meets a singular matrix
+    ///or does not converge, e.g. for eigenvalues on the negative real axis.
+    pub fn sqrt(&self) -> Option<Self> {
+        let mut y = *self;
+        let mut z = Self::identity_n();
+        let mut previous = None;
+
+        for _ in 0..MAX_ITERATIONS {
+            let (y_inv, z_inv) = (y.inverse()?, z.inverse()?);
+            let next = (y + z_inv) / S::TWO;
+            z = (z + y_inv) / S::TWO;
+
+            let change = (next - y).norm_1();
+            y = next;
+            if change == S::ZERO || previous.is_some_and(|p| change >= p) {
+                //Stagnation also happens on negative or complex eigenvalues, so the result has to be checked.
+                let tolerance = S::EPSILON.square_root2() * self.norm_1();
+                return crate::ternary!((y * y - *self).norm_1() <= tolerance => Some(y) ; None);
+            }
+            previous = Some(change);
+        }
+
+        None
+    }
+
+    ///Principal logarithm, the inverse of [`Matrix::exp`], by inverse scaling and squaring. `None`
+    ///when the matrix has no real logarithm reachable through [`Matrix::sqrt`].
+    pub fn ln(&self) -> Option<Self> {
+        let identity = Self::identity_n();
+        let mut x = *self;
+        let mut factor = S::ONE;
+        while (x - identity).norm_1() > S::ONE / S::FOUR {
+            if factor > S::from_f64(f64::from(1u32 << 30)) {
+                return None;
+            }
+            x = x.sqrt()?;
+            factor *= S::TWO;
+        }
+
+        //ln(I + E) = E - E²/2 + E³/3 - …
+        let e = x - identity;
+        let mut power = identity;
+        let mut out = Self::ZERO;
+        for k in 1..MAX_ITERATIONS {
+            power = power * e;
+            let term = power / S::from_f64(k as f64);
+            let next = crate::ternary!(k % 2 == 1 => out + term ; out - term);
+            if next == out {
+                break;
+            }
+            out = next;
+        }
+
+        Some(out * factor)
+    }
+}
@@ -1,9 +1,9 @@
-use crate::matrix::Matrix;
+use crate::matrix::{algorithms, Matrix};
 use crate::scalar::Scalar;
 use crate::vector::Vector;
 use std::ffi::c_void;
 use std::fmt::{Display, Formatter};
-use std::ops::{Add, Index, IndexMut, Sub};
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
 
 #[derive(Debug, Clone, Copy)]
 pub enum MatrixInitializer<const R: usize, const C: usize, S: Scalar> {
@@ -14,6 +14,8 @@ pub enum MatrixInitializer<const R: usize, const C: usize, S: Scalar> {
 impl<const R: usize, const C: usize, S: Scalar> Matrix<R, C, S> {
     pub const ZERO: Self = Self([Vector::ZERO; R]);
     pub const ONE: Self = Self([Vector::ONE; R]);
+    pub const ROWS: usize = R;
+    pub const COLS: usize = C;
 
     pub const fn from_array(array: [Vector<C, S>; R]) -> Self {
         Self(array)
@@ -27,6 +29,15 @@ impl<const R: usize, const C: usize, S: Scalar> Matrix<R, C, S> {
         self.as_ptr() as *const _
     }
 
+    ///The elements row after row.
+    pub fn as_slice(&self) -> &[S] {
+        unsafe { std::slice::from_raw_parts(self.0.as_ptr() as *const S, R * C) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [S] {
+        unsafe { std::slice::from_raw_parts_mut(self.0.as_mut_ptr() as *mut S, R * C) }
+    }
+
     pub fn longest_char(&self) -> (usize, usize, usize) {
         let mut length = 0;
         let mut length_pos = (0, 0);
@@ -44,6 +55,33 @@ impl<const R: usize, const C: usize, S: Scalar> Matrix<R, C, S> {
         (length_pos.0, length_pos.1, length)
     }
 
+    pub fn row(&self, row: usize) -> Vector<C, S> {
+        self[row]
+    }
+
+    pub fn col(&self, col: usize) -> Vector<R, S> {
+        let mut out = Vector::ZERO;
+        for row in 0..R {
+            out[row] = self[row][col];
+        }
+        out
+    }
+
+    ///Copies the overlapping block into a matrix of another size, padding with the identity.
+    ///Useful to go between affine `3x4`/`4x3` storage and full `4x4` transforms.
+    pub fn resized<const R2: usize, const C2: usize>(&self) -> Matrix<R2, C2, S> {
+        let mut out = Matrix::ZERO;
+        for row in 0..R2 {
+            for col in 0..C2 {
+                out[row][col] = match row < R && col < C {
+                    true => self[row][col],
+                    false => crate::ternary!(row == col => S::ONE ; S::ZERO),
+                };
+            }
+        }
+        out
+    }
+
     pub fn transpose(&self) -> Matrix<C, R, S> {
         let mut out = Matrix::ZERO;
 
@@ -57,6 +95,23 @@ impl<const R: usize, const C: usize, S: Scalar> Matrix<R, C, S> {
     }
 }
 
+impl<const N: usize, S: Scalar> Matrix<N, N, S> {
+    pub fn determinant(&self) -> S {
+        let mut m = *self;
+        algorithms::determinant(m.as_mut_slice(), N)
+    }
+
+    ///Gauss-Jordan elimination with partial pivoting, `None` when the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut m = *self;
+        let mut out = Self::ZERO;
+        match algorithms::invert(m.as_mut_slice(), out.as_mut_slice(), N) {
+            true => Some(out),
+            false => None,
+        }
+    }
+}
+
 impl<const R: usize, const C: usize, S: Scalar> Index<usize> for Matrix<R, C, S> {
     type Output = Vector<C, S>;
 
@@ -78,7 +133,7 @@ impl<const R: usize, const C: usize, S: Scalar> Add<Self> for Matrix<R, C, S> {
         let mut out = Matrix::ZERO;
         for row in 0..R {
             for col in 0..C {
-                out[col][row] = self[row][col] + rhs[row][col];
+                out[row][col] = self[row][col] + rhs[row][col];
             }
         }
         out
@@ -92,13 +147,83 @@ impl<const R: usize, const C: usize, S: Scalar> Sub<Self> for Matrix<R, C, S> {
         let mut out = Matrix::ZERO;
         for row in 0..R {
             for col in 0..C {
-                out[col][row] = self[row][col] - rhs[row][col];
+                out[row][col] = self[row][col] - rhs[row][col];
             }
         }
         out
     }
 }
 
+impl<const R: usize, const C: usize, S: Scalar> Neg for Matrix<R, C, S> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut out = self;
+        for row in 0..R {
+            out[row] = -self[row];
+        }
+        out
+    }
+}
+
+impl<const R: usize, const C: usize, S: Scalar> Mul<S> for Matrix<R, C, S> {
+    type Output = Self;
+
+    fn mul(self, rhs: S) -> Self::Output {
+        let mut out = self;
+        for row in 0..R {
+            out[row] = self[row] * rhs;
+        }
+        out
+    }
+}
+
+impl<const R: usize, const C: usize, S: Scalar> Div<S> for Matrix<R, C, S> {
+    type Output = Self;
+
+    fn div(self, rhs: S) -> Self::Output {
+        self * (S::ONE / rhs)
+    }
+}
+
+///Plain matrix product. Which operand is applied first depends on the
+///[`CONVENTION`](crate::matrix::convention::CONVENTION); use [`Matrix::then`] for order-independent composition.
+impl<const R: usize, const C: usize, const K: usize, S: Scalar> Mul<Matrix<C, K, S>> for Matrix<R, C, S> {
+    type Output = Matrix<R, K, S>;
+
+    fn mul(self, rhs: Matrix<C, K, S>) -> Self::Output {
+        let mut out = Matrix::ZERO;
+        algorithms::multiply(self.as_slice(), rhs.as_slice(), out.as_mut_slice(), R, C, K);
+        out
+    }
+}
+
+///Column vector product `M * v`.
+impl<const R: usize, const C: usize, S: Scalar> Mul<Vector<C, S>> for Matrix<R, C, S> {
+    type Output = Vector<R, S>;
+
+    fn mul(self, rhs: Vector<C, S>) -> Self::Output {
+        let mut out = Vector::ZERO;
+        for row in 0..R {
+            out[row] = self[row].dot_product(&rhs);
+        }
+        out
+    }
+}
+
+///Row vector product `v * M`.
+impl<const R: usize, const C: usize, S: Scalar> Mul<Matrix<R, C, S>> for Vector<R, S> {
+    type Output = Vector<C, S>;
+
+    fn mul(self, rhs: Matrix<R, C, S>) -> Self::Output {
+        let mut out = Vector::ZERO;
+        for row in 0..R {
+            out = out + rhs[row] * self[row];
+        }
+        out
+    }
+}
+
 #[macro_export]
 macro_rules! lua_matrix_methods {
     ($methods:ident) => {};
@@ -117,6 +242,7 @@ macro_rules! lua_matrix {
             methods = {
                 $(fn $method_name:ident($this:ident, $method_args:ident: $method_args_type:ty) $method_block:block)*
             }
+            mul = { $($mul_rhs:ty),* }
             meta_method = {
                 $($meta_method:path[$meta_this:ident] => $arg_name:ident: $arg_ty:ty $meta_method_body:block)*
             }
@@ -125,8 +251,12 @@ macro_rules! lua_matrix {
         impl rlua::UserData for $t {
             fn add_methods<'lua, M: rlua::UserDataMethods<'lua, Self>>(methods: &mut M) {
                 methods.add_meta_method(rlua::MetaMethod::ToString, |_, this, _: ()| Ok(this.to_string()));
-                methods.add_meta_method(rlua::MetaMethod::Index, |_, this, index: usize| Ok(this[index]));
+                methods.add_meta_method(rlua::MetaMethod::Index, |_, this, index: usize| {
+                    Ok(this[crate::lua::check_bounds(index, Self::ROWS)?])
+                });
                 methods.add_method_mut("set", |_, this, (c, r, v): (usize, usize, $f)| {
+                    let r = crate::lua::check_bounds(r, Self::ROWS)?;
+                    let c = crate::lua::check_bounds(c, Self::COLS)?;
                     this[r][c] = v;
                     Ok(())
                 });
@@ -135,6 +265,48 @@ macro_rules! lua_matrix {
                     Ok(this.transpose())
                 });
 
+                methods.add_method("row", |_, this, row: usize| {
+                    Ok(this.row(crate::lua::check_bounds(row, Self::ROWS)?))
+                });
+                methods.add_method("col", |_, this, col: usize| {
+                    Ok(this.col(crate::lua::check_bounds(col, Self::COLS)?))
+                });
+                methods.add_method("frobenius_norm", |_, this, _: ()| Ok(this.frobenius_norm()));
+                methods.add_method("norm_1", |_, this, _: ()| Ok(this.norm_1()));
+                methods.add_method("norm_inf", |_, this, _: ()| Ok(this.norm_inf()));
+                methods.add_method("spectral_norm", |_, this, _: ()| Ok(this.spectral_norm()));
+                methods.add_method("rank", |_, this, _: ()| Ok(this.rank()));
+                methods.add_method("hadamard", |_, this, b: Self| Ok(this.hadamard(&b)));
+
+                methods.add_meta_method(rlua::MetaMethod::Eq, |_, this, b: Self| Ok(*this == b));
+                methods.add_meta_method(rlua::MetaMethod::Unm, |_, this, _: ()| Ok(-*this));
+                methods.add_meta_method(rlua::MetaMethod::Add, |_, this, b: Self| Ok(*this + b));
+                methods.add_meta_method(rlua::MetaMethod::Sub, |_, this, b: Self| Ok(*this - b));
+
+                methods.add_meta_function(rlua::MetaMethod::Mul, |lua, (a, b): (rlua::Value, rlua::Value)| {
+                    match (a, b) {
+                        (rlua::Value::UserData(a), rlua::Value::UserData(b)) => {
+                            let a = *a.borrow::<Self>()?;
+                            $(
+                                if let Ok(b) = b.borrow::<$mul_rhs>() {
+                                    return rlua::IntoLua::into_lua(a * *b, lua);
+                                }
+                            )*
+                            Err(rlua::Error::RuntimeError(format!(
+                                "cannot multiply {} by {}", stringify!($t), crate::lua::type_name(&b)
+                            )))
+                        }
+                        (rlua::Value::UserData(m), s) | (s, rlua::Value::UserData(m)) => {
+                            rlua::IntoLua::into_lua(*m.borrow::<Self>()? * <$f as rlua::FromLua>::from_lua(s, lua)?, lua)
+                        }
+                        (a, b) => Err(rlua::Error::RuntimeError(format!(
+                            "cannot multiply {} by {}", a.type_name(), b.type_name()
+                        ))),
+                    }
+                });
+
+                methods.add_meta_function(rlua::MetaMethod::Div, |_, (this, s): (Self, $f)| Ok(this / s));
+
                 $(
                     methods.add_method(stringify!($method_name), |_, $this, $method_args: $method_args_type| $method_block);
                 )*
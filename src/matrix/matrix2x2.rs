@@ -1,10 +1,9 @@
-use std::ops::Mul;
-use mlua::MetaMethod;
 use crate::matrix::imp::MatrixInitializer;
 use crate::matrix::square_matrix::SquareMatrix;
+use crate::matrix::matrix2x3::{Matrix2x3D, Matrix2x3F};
 use crate::matrix::Matrix;
 use crate::scalar::Scalar;
-use crate::vector::vec2::Vec2F;
+use crate::vector::vec2::{Vec2D, Vec2F};
 use crate::vector::Vector;
 
 pub type Matrix2x2Initializer<S> = MatrixInitializer<2, 2, S>;
@@ -13,23 +12,6 @@ pub type Matrix2x2<S> = Matrix<2, 2, S>;
 pub type Matrix2x2F = Matrix2x2<f32>;
 pub type Matrix2x2D = Matrix2x2<f64>;
 
-impl<S: Scalar> Mul<Self> for Matrix2x2<S> {
-    type Output = Matrix2x2<S>;
-
-    fn mul(self, rhs: Self) -> Self::Output {
-        let a = self.0;
-        let b = rhs.0;
-
-        let mut c = [Vector::ZERO; 2];
-        c[0][0] = a[0][0] * b[0][0] + a[0][1] * b[1][0];
-        c[0][1] = a[0][0] * b[0][1] + a[0][1] * b[1][1];
-        c[1][0] = a[1][0] * b[0][0] + a[1][1] * b[1][0];
-        c[1][1] = a[1][0] * b[0][1] + a[1][1] * b[1][1];
-
-        Self::from_array(c)
-    }
-}
-
 impl <S: Scalar> SquareMatrix<S> for Matrix2x2<S> {
     const IDENTITY: Self = Matrix::from_array([
         Vector::from_array([S::ONE, S::ZERO]),
@@ -66,11 +48,59 @@ crate::lua_matrix!(Matrix2x2F => f32 {
         }
     ]
     methods = {
+        fn power(this, n: i32) {
+            Ok(this.power(n))
+        }
 
+        fn exp(this, _args: ()) {
+            Ok(this.exp())
+        }
+
+        fn ln(this, _args: ()) {
+            Ok(this.ln())
+        }
+
+        fn sqrt(this, _args: ()) {
+            Ok(this.sqrt())
+        }
+    }
+    mul = { Self, Matrix2x3F, Vec2F }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix2x2D => f64 {
+    Args = (Option<Vec2D>, Option<Vec2D>),
+    CONSTRUCTOR_NAME = "mat2x2d",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec2D::ZERO),
+                args.1.unwrap_or(Vec2D::ZERO)
+            ]))
+        )
     }
-    meta_method = {
-        MetaMethod::Mul[this] => b: Self {
-            Ok(*this * b)
+    associated_functions = (lua) [
+        fn mat2x2d_identity(arg: Option<f64>) {
+            Ok(Self::identity(arg))
+        }
+    ]
+    methods = {
+        fn power(this, n: i32) {
+            Ok(this.power(n))
+        }
+
+        fn exp(this, _args: ()) {
+            Ok(this.exp())
+        }
+
+        fn ln(this, _args: ()) {
+            Ok(this.ln())
+        }
+
+        fn sqrt(this, _args: ()) {
+            Ok(this.sqrt())
         }
     }
-});
\ No newline at end of file
+    mul = { Self, Matrix2x3D, Vec2D }
+    meta_method = {}
+});
@@ -0,0 +1,97 @@
+use crate::matrix::matrix3x2::{Matrix3x2D, Matrix3x2F};
+use crate::matrix::matrix3x3::{Matrix3x3, Matrix3x3D, Matrix3x3F};
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::vector::vec2::{Vec2, Vec2D, Vec2F};
+use crate::vector::vec3::{Vec3D, Vec3F};
+
+///2D affine transform for column vectors: a [`Matrix3x3`] without its projective last row.
+pub type Matrix2x3<S> = Matrix<2, 3, S>;
+pub type Matrix2x3F = Matrix2x3<f32>;
+pub type Matrix2x3D = Matrix2x3<f64>;
+
+impl<S: Scalar> Matrix2x3<S> {
+    ///Drops the projective last row of `m`, which is in the crate's [`CONVENTION`](crate::matrix::convention::CONVENTION).
+    pub fn from_3x3(m: Matrix3x3<S>) -> Self {
+        m.conventional().resized()
+    }
+
+    ///Restores the `0 .. 0 1` last row and returns the result in the crate's convention.
+    pub fn to_3x3(&self) -> Matrix3x3<S> {
+        self.resized::<3, 3>().conventional()
+    }
+
+    pub fn transform_point(&self, point: Vec2<S>) -> Vec2<S> {
+        *self * point.to_point()
+    }
+
+    pub fn transform_vector(&self, vector: Vec2<S>) -> Vec2<S> {
+        *self * vector.to_direction()
+    }
+}
+
+crate::lua_matrix!(Matrix2x3F => f32 {
+    Args = (Option<Vec3F>, Option<Vec3F>),
+    CONSTRUCTOR_NAME = "mat2x3f",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec3F::ZERO),
+                args.1.unwrap_or(Vec3F::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat2x3f_from_mat3x3(m: Matrix3x3F) {
+            Ok(Self::from_3x3(m))
+        }
+    ]
+    methods = {
+        fn to_mat3x3(this, _args: ()) {
+            Ok(this.to_3x3())
+        }
+
+        fn transform_point(this, point: Vec2F) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec2F) {
+            Ok(this.transform_vector(vector))
+        }
+    }
+    mul = { Matrix3x3F, Matrix3x2F, Vec3F }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix2x3D => f64 {
+    Args = (Option<Vec3D>, Option<Vec3D>),
+    CONSTRUCTOR_NAME = "mat2x3d",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec3D::ZERO),
+                args.1.unwrap_or(Vec3D::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat2x3d_from_mat3x3(m: Matrix3x3D) {
+            Ok(Self::from_3x3(m))
+        }
+    ]
+    methods = {
+        fn to_mat3x3(this, _args: ()) {
+            Ok(this.to_3x3())
+        }
+
+        fn transform_point(this, point: Vec2D) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec2D) {
+            Ok(this.transform_vector(vector))
+        }
+    }
+    mul = { Matrix3x3D, Matrix3x2D, Vec3D }
+    meta_method = {}
+});
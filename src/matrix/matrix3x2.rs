@@ -0,0 +1,99 @@
+use crate::matrix::matrix2x2::{Matrix2x2D, Matrix2x2F};
+use crate::matrix::matrix2x3::{Matrix2x3D, Matrix2x3F};
+use crate::matrix::matrix3x3::{Matrix3x3, Matrix3x3D, Matrix3x3F};
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::vector::vec2::{Vec2, Vec2D, Vec2F};
+
+///2D affine transform for row vectors: a [`Matrix3x3`] without its projective last column.
+pub type Matrix3x2<S> = Matrix<3, 2, S>;
+pub type Matrix3x2F = Matrix3x2<f32>;
+pub type Matrix3x2D = Matrix3x2<f64>;
+
+impl<S: Scalar> Matrix3x2<S> {
+    ///Drops the projective last column of `m`, which is in the crate's [`CONVENTION`](crate::matrix::convention::CONVENTION).
+    pub fn from_3x3(m: Matrix3x3<S>) -> Self {
+        m.conventional().transpose().resized()
+    }
+
+    ///Restores the `0 .. 0 1` last column and returns the result in the crate's convention.
+    pub fn to_3x3(&self) -> Matrix3x3<S> {
+        self.resized::<3, 3>().transpose().conventional()
+    }
+
+    pub fn transform_point(&self, point: Vec2<S>) -> Vec2<S> {
+        point.to_point() * *self
+    }
+
+    pub fn transform_vector(&self, vector: Vec2<S>) -> Vec2<S> {
+        vector.to_direction() * *self
+    }
+}
+
+crate::lua_matrix!(Matrix3x2F => f32 {
+    Args = (Option<Vec2F>, Option<Vec2F>, Option<Vec2F>),
+    CONSTRUCTOR_NAME = "mat3x2f",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec2F::ZERO),
+                args.1.unwrap_or(Vec2F::ZERO),
+                args.2.unwrap_or(Vec2F::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat3x2f_from_mat3x3(m: Matrix3x3F) {
+            Ok(Self::from_3x3(m))
+        }
+    ]
+    methods = {
+        fn to_mat3x3(this, _args: ()) {
+            Ok(this.to_3x3())
+        }
+
+        fn transform_point(this, point: Vec2F) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec2F) {
+            Ok(this.transform_vector(vector))
+        }
+    }
+    mul = { Matrix2x2F, Matrix2x3F, Vec2F }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix3x2D => f64 {
+    Args = (Option<Vec2D>, Option<Vec2D>, Option<Vec2D>),
+    CONSTRUCTOR_NAME = "mat3x2d",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec2D::ZERO),
+                args.1.unwrap_or(Vec2D::ZERO),
+                args.2.unwrap_or(Vec2D::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat3x2d_from_mat3x3(m: Matrix3x3D) {
+            Ok(Self::from_3x3(m))
+        }
+    ]
+    methods = {
+        fn to_mat3x3(this, _args: ()) {
+            Ok(this.to_3x3())
+        }
+
+        fn transform_point(this, point: Vec2D) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec2D) {
+            Ok(this.transform_vector(vector))
+        }
+    }
+    mul = { Matrix2x2D, Matrix2x3D, Vec2D }
+    meta_method = {}
+});
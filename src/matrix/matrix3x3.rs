@@ -1,23 +1,42 @@
 use mlua::{AnyUserData, FromLua};
-use crate::angle::{Angle, AngleD, AngleF};
-use crate::euler::{Euler, EulerF};
+use crate::angle::{Angle, AngleD, AngleF, AngleX};
+use crate::euler::{Euler, EulerD, EulerF, EulerX};
+use crate::fixed::Q16_16;
 use crate::matrix::imp::MatrixInitializer;
 use crate::matrix::square_matrix::SquareMatrix;
+use crate::matrix::matrix3x2::{Matrix3x2D, Matrix3x2F};
+use crate::matrix::matrix3x4::{Matrix3x4D, Matrix3x4F};
 use crate::matrix::Matrix;
 use crate::scalar::Scalar;
-use crate::vector::vec3::{Vec3, Vec3F};
-use crate::vector::Vector;
+use crate::vector::vec2::{Vec2, Vec2D, Vec2F, Vec2X};
+use crate::vector::vec3::{Vec3, Vec3D, Vec3F, Vec3X};
 
 pub type Matrix3x3<S> = Matrix<3, 3, S>;
 pub type Matrix3x3F = Matrix3x3<f32>;
+pub type Matrix3x3D = Matrix3x3<f64>;
+pub type Matrix3x3X = Matrix3x3<Q16_16>;
 pub type Matrix3x3Initializer<S> = MatrixInitializer<3, 3, S>;
 
-#[derive(Debug, Clone, Copy, FromLua)]
+///Order in which the axis rotations of an [`Euler`] are composed, named after the matrix product.
+///`ZYX` is `Rz(yaw)·Ry(pitch)·Rx(roll)`, applying roll first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RotationOrder {
+    #[default]
     ZYX,
     XYZ
 }
 
+///Read from the strings `"ZYX"` and `"XYZ"`.
+impl<'lua> FromLua<'lua> for RotationOrder {
+    fn from_lua(value: mlua::Value<'lua>, lua: &'lua mlua::Lua) -> mlua::Result<Self> {
+        match String::from_lua(value, lua)?.to_uppercase().as_str() {
+            "ZYX" => Ok(Self::ZYX),
+            "XYZ" => Ok(Self::XYZ),
+            other => Err(mlua::Error::RuntimeError(format!("unknown rotation order {other}"))),
+        }
+    }
+}
+
 impl <S: Scalar> Matrix3x3<S> {
     pub fn x_rotation(theta: Angle<S>) -> Self {
         let one = S::ONE;
@@ -33,7 +52,7 @@ impl <S: Scalar> Matrix3x3<S> {
         out[1][2] = -sin;
         out[2][1] = sin;
 
-        out
+        out.conventional()
     }
 
     pub fn y_rotation(theta: Angle<S>) -> Self {
@@ -49,7 +68,7 @@ impl <S: Scalar> Matrix3x3<S> {
         out[2][0] = -sin;
         out[2][2] = cos;
 
-        out
+        out.conventional()
     }
     pub fn z_rotation(theta: Angle<S>) -> Self {
         let one = S::ONE;
@@ -63,39 +82,71 @@ impl <S: Scalar> Matrix3x3<S> {
         out[1][1] = cos;
         out[2][2] = one;
 
-        out
+        out.conventional()
     }
 
-    pub fn rotation(euler: Euler<S>, rotation_order: RotationOrder) -> Self {
-        let (yaw, pitch, roll) = euler.take_radians();
+    ///Transforms a 2D point, including translation and the perspective divide.
+    pub fn transform_point(&self, point: Vec2<S>) -> Vec2<S> {
+        let v = self.transform(point.to_point());
+        match v[2] == S::ZERO {
+            true => v.down(),
+            false => v.down() * (S::ONE / v[2]),
+        }
+    }
+
+    ///Transforms a 2D direction, ignoring translation.
+    pub fn transform_vector(&self, vector: Vec2<S>) -> Vec2<S> {
+        self.transform(vector.to_direction()).down()
+    }
+
+    ///Transforms a 2D surface normal by the inverse-transpose. The result is normalized, `None` when the matrix is singular.
+    pub fn transform_normal(&self, normal: Vec2<S>) -> Option<Vec2<S>> {
+        let inverse_transpose = self.inverse()?.transpose();
+        Some(inverse_transpose.transform_vector(normal).normalized())
+    }
 
-        let (yaw_sin, yaw_cos) = (yaw.sine(), yaw.cosine());
-        let (pitch_sin, pitch_cos) = (pitch.sine(), pitch.cosine());
-        let (roll_sin, roll_cos) = (roll.sine(), roll.cosine());
+    ///Rotation matrix for `euler`, composed in `rotation_order`.
+    pub fn rotation(euler: Euler<S>, rotation_order: RotationOrder) -> Self {
+        let (x, y, z) = (
+            Self::x_rotation(euler.roll),
+            Self::y_rotation(euler.pitch),
+            Self::z_rotation(euler.yaw),
+        );
 
         match rotation_order {
+            RotationOrder::ZYX => x.then(y).then(z),
+            RotationOrder::XYZ => z.then(y).then(x),
+        }
+    }
+
+    ///Euler angles in radians that [`Matrix3x3::rotation`] turns back into this rotation. Pitch is kept
+    ///in `[-90°, 90°]`. At gimbal lock, where yaw and roll share an axis, roll is zero and yaw carries
+    ///the whole turn.
+    pub fn to_euler(&self, rotation_order: RotationOrder) -> Euler<S> {
+        let r = self.conventional();
+        //Below this cos(pitch) yaw and roll can no longer be told apart.
+        let locked = S::from_f64(16.0) * S::EPSILON;
+
+        let (yaw, pitch, roll) = match rotation_order {
             RotationOrder::ZYX => {
-                eprintln!("Remember to test: [{}] {}:{}", file!(), line!(), column!());
-                Self::from_array([
-                    Vector::from_array([
-                        yaw_cos * pitch_cos,
-                        yaw_cos * pitch_sin * roll_sin - yaw_sin * roll_cos,
-                        yaw_cos * pitch_sin + yaw_sin * roll_sin
-                    ]),
-                    Vector::from_array([
-                        yaw_sin * pitch_cos,
-                         yaw_sin * pitch_sin * roll_sin + yaw_cos * roll_cos,
-                        yaw_sin * pitch_sin * roll_cos - yaw_cos * roll_sin
-                    ]),
-                    Vector::from_array([
-                        -pitch_sin,
-                        pitch_cos * roll_sin,
-                        pitch_cos * roll_cos
-                    ])
-                ])
+                let cos_pitch = (r[0][0].squared() + r[1][0].squared()).square_root2();
+                let pitch = (-r[2][0]).inverse_tangent2(cos_pitch);
+                match cos_pitch > locked {
+                    true => (r[1][0].inverse_tangent2(r[0][0]), pitch, r[2][1].inverse_tangent2(r[2][2])),
+                    false => ((-r[0][1]).inverse_tangent2(r[1][1]), pitch, S::ZERO),
+                }
             }
-            RotationOrder::XYZ => todo!()
-        }
+            RotationOrder::XYZ => {
+                let cos_pitch = (r[0][0].squared() + r[0][1].squared()).square_root2();
+                let pitch = r[0][2].inverse_tangent2(cos_pitch);
+                match cos_pitch > locked {
+                    true => ((-r[0][1]).inverse_tangent2(r[0][0]), pitch, (-r[1][2]).inverse_tangent2(r[2][2])),
+                    false => (r[1][0].inverse_tangent2(r[1][1]), pitch, S::ZERO),
+                }
+            }
+        };
+
+        Euler::new(Angle::Radians(yaw), Angle::Radians(pitch), Angle::Radians(roll)).with_order(rotation_order)
     }
 }
 
@@ -126,7 +177,7 @@ crate::lua_matrix!(Matrix3x3F => f32 {
         lua.create_function(|_, args: Self::Args| Ok(Self::from_array([
             args.0.unwrap_or(Vec3F::ZERO),
             args.1.unwrap_or(Vec3F::ZERO),
-            args.1.unwrap_or(Vec3F::ZERO)
+            args.2.unwrap_or(Vec3F::ZERO)
         ])))
     }
     associated_functions = (lua) [
@@ -171,17 +222,188 @@ crate::lua_matrix!(Matrix3x3F => f32 {
         }
 
         fn mat3x3f_rotation(data: (Option<EulerF>, Option<RotationOrder>)) {
-            let rotation = data.0.unwrap_or(EulerF {
-                yaw: Angle::RAD_ZERO,
-                pitch: Angle::RAD_ZERO,
-                roll: Angle::RAD_ZERO,
-                debug_flag: 0
-            });
-
-            let order = data.1.unwrap_or(RotationOrder::ZYX);
+            let rotation = data.0.unwrap_or(EulerF::new(Angle::RAD_ZERO, Angle::RAD_ZERO, Angle::RAD_ZERO));
+            let order = data.1.unwrap_or(rotation.order);
+            Ok(Matrix::rotation(rotation, order))
+        }
+    ]
+    methods = {
+        fn to_euler(this, order: Option<RotationOrder>) {
+            Ok(this.to_euler(order.unwrap_or_default()))
+        }
+
+        fn power(this, n: i32) {
+            Ok(this.power(n))
+        }
+
+        fn exp(this, _args: ()) {
+            Ok(this.exp())
+        }
+
+        fn ln(this, _args: ()) {
+            Ok(this.ln())
+        }
+
+        fn sqrt(this, _args: ()) {
+            Ok(this.sqrt())
+        }
+
+        fn transform_point(this, point: Vec2F) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec2F) {
+            Ok(this.transform_vector(vector))
+        }
+
+        fn transform_normal(this, normal: Vec2F) {
+            Ok(this.transform_normal(normal))
+        }
+
+        fn inverse(this, _args: ()) {
+            Ok(this.inverse())
+        }
+
+        fn determinant(this, _args: ()) {
+            Ok(this.determinant())
+        }
+    }
+    mul = { Self, Matrix3x2F, Matrix3x4F, Vec3F }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix3x3D => f64 {
+    Args = (Option<Vec3D>, Option<Vec3D>, Option<Vec3D>),
+    CONSTRUCTOR_NAME = "mat3x3d",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args| Ok(Self::from_array([
+            args.0.unwrap_or(Vec3D::ZERO),
+            args.1.unwrap_or(Vec3D::ZERO),
+            args.2.unwrap_or(Vec3D::ZERO)
+        ])))
+    }
+    associated_functions = (lua) [
+        fn mat3x3d_identity(arg: Option<f64>) {
+            Ok(Self::identity(arg))
+        }
+
+        fn mat3x3d_x_rotation(angle: AngleD) {
+            Ok(Self::x_rotation(angle))
+        }
+
+        fn mat3x3d_y_rotation(angle: AngleD) {
+            Ok(Self::y_rotation(angle))
+        }
+
+        fn mat3x3d_z_rotation(angle: AngleD) {
+            Ok(Self::z_rotation(angle))
+        }
+
+        fn mat3x3d_rotation(data: (Option<EulerD>, Option<RotationOrder>)) {
+            let rotation = data.0.unwrap_or(EulerD::new(Angle::RAD_ZERO, Angle::RAD_ZERO, Angle::RAD_ZERO));
+            let order = data.1.unwrap_or(rotation.order);
             Ok(Matrix::rotation(rotation, order))
         }
     ]
-    methods = {}
+    methods = {
+        fn to_euler(this, order: Option<RotationOrder>) {
+            Ok(this.to_euler(order.unwrap_or_default()))
+        }
+
+        fn power(this, n: i32) {
+            Ok(this.power(n))
+        }
+
+        fn exp(this, _args: ()) {
+            Ok(this.exp())
+        }
+
+        fn ln(this, _args: ()) {
+            Ok(this.ln())
+        }
+
+        fn sqrt(this, _args: ()) {
+            Ok(this.sqrt())
+        }
+
+        fn transform_point(this, point: Vec2D) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec2D) {
+            Ok(this.transform_vector(vector))
+        }
+
+        fn transform_normal(this, normal: Vec2D) {
+            Ok(this.transform_normal(normal))
+        }
+
+        fn inverse(this, _args: ()) {
+            Ok(this.inverse())
+        }
+
+        fn determinant(this, _args: ()) {
+            Ok(this.determinant())
+        }
+    }
+    mul = { Self, Matrix3x2D, Matrix3x4D, Vec3D }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix3x3X => Q16_16 {
+    Args = (Option<Vec3X>, Option<Vec3X>, Option<Vec3X>),
+    CONSTRUCTOR_NAME = "mat3x3x",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args| Ok(Self::from_array([
+            args.0.unwrap_or(Vec3X::ZERO),
+            args.1.unwrap_or(Vec3X::ZERO),
+            args.2.unwrap_or(Vec3X::ZERO)
+        ])))
+    }
+    associated_functions = (lua) [
+        fn mat3x3x_identity(arg: Option<Q16_16>) {
+            Ok(Self::identity(arg))
+        }
+
+        fn mat3x3x_x_rotation(angle: AngleX) {
+            Ok(Self::x_rotation(angle))
+        }
+
+        fn mat3x3x_y_rotation(angle: AngleX) {
+            Ok(Self::y_rotation(angle))
+        }
+
+        fn mat3x3x_z_rotation(angle: AngleX) {
+            Ok(Self::z_rotation(angle))
+        }
+
+        fn mat3x3x_rotation(data: (Option<EulerX>, Option<RotationOrder>)) {
+            let rotation = data.0.unwrap_or(EulerX::new(Angle::RAD_ZERO, Angle::RAD_ZERO, Angle::RAD_ZERO));
+            let order = data.1.unwrap_or(rotation.order);
+            Ok(Matrix::rotation(rotation, order))
+        }
+    ]
+    methods = {
+        fn to_euler(this, order: Option<RotationOrder>) {
+            Ok(this.to_euler(order.unwrap_or_default()))
+        }
+
+        fn transform_point(this, point: Vec2X) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec2X) {
+            Ok(this.transform_vector(vector))
+        }
+
+        fn inverse(this, _args: ()) {
+            Ok(this.inverse())
+        }
+
+        fn determinant(this, _args: ()) {
+            Ok(this.determinant())
+        }
+    }
+    mul = { Self, Vec3X }
     meta_method = {}
-});
\ No newline at end of file
+});
@@ -0,0 +1,100 @@
+use crate::matrix::matrix4x3::{Matrix4x3D, Matrix4x3F};
+use crate::matrix::matrix4x4::{Matrix4x4, Matrix4x4D, Matrix4x4F};
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::vector::vec3::{Vec3, Vec3D, Vec3F};
+use crate::vector::vec4::{Vec4D, Vec4F};
+
+///3D affine transform for column vectors: a [`Matrix4x4`] without its projective last row,
+///the usual layout for GPU-packed bone matrices.
+pub type Matrix3x4<S> = Matrix<3, 4, S>;
+pub type Matrix3x4F = Matrix3x4<f32>;
+pub type Matrix3x4D = Matrix3x4<f64>;
+
+impl<S: Scalar> Matrix3x4<S> {
+    ///Drops the projective last row of `m`, which is in the crate's [`CONVENTION`](crate::matrix::convention::CONVENTION).
+    pub fn from_4x4(m: Matrix4x4<S>) -> Self {
+        m.conventional().resized()
+    }
+
+    ///Restores the `0 .. 0 1` last row and returns the result in the crate's convention.
+    pub fn to_4x4(&self) -> Matrix4x4<S> {
+        self.resized::<4, 4>().conventional()
+    }
+
+    pub fn transform_point(&self, point: Vec3<S>) -> Vec3<S> {
+        *self * point.to_point()
+    }
+
+    pub fn transform_vector(&self, vector: Vec3<S>) -> Vec3<S> {
+        *self * vector.to_direction()
+    }
+}
+
+crate::lua_matrix!(Matrix3x4F => f32 {
+    Args = (Option<Vec4F>, Option<Vec4F>, Option<Vec4F>),
+    CONSTRUCTOR_NAME = "mat3x4f",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec4F::ZERO),
+                args.1.unwrap_or(Vec4F::ZERO),
+                args.2.unwrap_or(Vec4F::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat3x4f_from_mat4x4(m: Matrix4x4F) {
+            Ok(Self::from_4x4(m))
+        }
+    ]
+    methods = {
+        fn to_mat4x4(this, _args: ()) {
+            Ok(this.to_4x4())
+        }
+
+        fn transform_point(this, point: Vec3F) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec3F) {
+            Ok(this.transform_vector(vector))
+        }
+    }
+    mul = { Matrix4x4F, Matrix4x3F, Vec4F }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix3x4D => f64 {
+    Args = (Option<Vec4D>, Option<Vec4D>, Option<Vec4D>),
+    CONSTRUCTOR_NAME = "mat3x4d",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec4D::ZERO),
+                args.1.unwrap_or(Vec4D::ZERO),
+                args.2.unwrap_or(Vec4D::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat3x4d_from_mat4x4(m: Matrix4x4D) {
+            Ok(Self::from_4x4(m))
+        }
+    ]
+    methods = {
+        fn to_mat4x4(this, _args: ()) {
+            Ok(this.to_4x4())
+        }
+
+        fn transform_point(this, point: Vec3D) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec3D) {
+            Ok(this.transform_vector(vector))
+        }
+    }
+    mul = { Matrix4x4D, Matrix4x3D, Vec4D }
+    meta_method = {}
+});
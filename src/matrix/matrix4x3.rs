@@ -0,0 +1,101 @@
+use crate::matrix::matrix3x3::{Matrix3x3D, Matrix3x3F};
+use crate::matrix::matrix3x4::{Matrix3x4D, Matrix3x4F};
+use crate::matrix::matrix4x4::{Matrix4x4, Matrix4x4D, Matrix4x4F};
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::vector::vec3::{Vec3, Vec3D, Vec3F};
+
+///3D affine transform for row vectors: a [`Matrix4x4`] without its projective last column.
+pub type Matrix4x3<S> = Matrix<4, 3, S>;
+pub type Matrix4x3F = Matrix4x3<f32>;
+pub type Matrix4x3D = Matrix4x3<f64>;
+
+impl<S: Scalar> Matrix4x3<S> {
+    ///Drops the projective last column of `m`, which is in the crate's [`CONVENTION`](crate::matrix::convention::CONVENTION).
+    pub fn from_4x4(m: Matrix4x4<S>) -> Self {
+        m.conventional().transpose().resized()
+    }
+
+    ///Restores the `0 .. 0 1` last column and returns the result in the crate's convention.
+    pub fn to_4x4(&self) -> Matrix4x4<S> {
+        self.resized::<4, 4>().transpose().conventional()
+    }
+
+    pub fn transform_point(&self, point: Vec3<S>) -> Vec3<S> {
+        point.to_point() * *self
+    }
+
+    pub fn transform_vector(&self, vector: Vec3<S>) -> Vec3<S> {
+        vector.to_direction() * *self
+    }
+}
+
+crate::lua_matrix!(Matrix4x3F => f32 {
+    Args = (Option<Vec3F>, Option<Vec3F>, Option<Vec3F>, Option<Vec3F>),
+    CONSTRUCTOR_NAME = "mat4x3f",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec3F::ZERO),
+                args.1.unwrap_or(Vec3F::ZERO),
+                args.2.unwrap_or(Vec3F::ZERO),
+                args.3.unwrap_or(Vec3F::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat4x3f_from_mat4x4(m: Matrix4x4F) {
+            Ok(Self::from_4x4(m))
+        }
+    ]
+    methods = {
+        fn to_mat4x4(this, _args: ()) {
+            Ok(this.to_4x4())
+        }
+
+        fn transform_point(this, point: Vec3F) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec3F) {
+            Ok(this.transform_vector(vector))
+        }
+    }
+    mul = { Matrix3x3F, Matrix3x4F, Vec3F }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix4x3D => f64 {
+    Args = (Option<Vec3D>, Option<Vec3D>, Option<Vec3D>, Option<Vec3D>),
+    CONSTRUCTOR_NAME = "mat4x3d",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec3D::ZERO),
+                args.1.unwrap_or(Vec3D::ZERO),
+                args.2.unwrap_or(Vec3D::ZERO),
+                args.3.unwrap_or(Vec3D::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat4x3d_from_mat4x4(m: Matrix4x4D) {
+            Ok(Self::from_4x4(m))
+        }
+    ]
+    methods = {
+        fn to_mat4x4(this, _args: ()) {
+            Ok(this.to_4x4())
+        }
+
+        fn transform_point(this, point: Vec3D) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec3D) {
+            Ok(this.transform_vector(vector))
+        }
+    }
+    mul = { Matrix3x3D, Matrix3x4D, Vec3D }
+    meta_method = {}
+});
@@ -1,16 +1,49 @@
-use crate::angle::Angle;
+use crate::angle::{Angle, AngleD, AngleF, AngleX};
+use crate::fixed::Q16_16;
+use crate::matrix::matrix3x3::{Matrix3x3, Matrix3x3D, Matrix3x3F};
 use crate::matrix::square_matrix::SquareMatrix;
+use crate::matrix::projection::{Frustum, ProjectionConfig, Viewport};
+use crate::matrix::matrix4x3::{Matrix4x3D, Matrix4x3F};
 use crate::matrix::Matrix;
+use crate::quaternion::{QuatD, QuatF, QuatX, Quaternion};
 use crate::scalar::Scalar;
-use crate::vector::vec3::{Vec3, Vec3F};
-use crate::vector::vec4::{Vec4, Vec4F};
+use crate::vector::vec3::{Vec3, Vec3D, Vec3F, Vec3X};
+use crate::vector::vec2::{Vec2D, Vec2F};
+use crate::vector::vec4::{Vec4, Vec4D, Vec4F, Vec4X};
 use crate::vector::Vector;
-use mlua::{AnyUserData, MetaMethod};
-use std::ops::Mul;
+use mlua::AnyUserData;
+use std::fmt::{Display, Formatter};
 
 pub type Matrix4x4<S> = Matrix<4, 4, S>;
 pub type Matrix4x4F = Matrix4x4<f32>;
 pub type Matrix4x4D = Matrix4x4<f64>;
+pub type Matrix4x4X = Matrix4x4<Q16_16>;
+
+///`(translation, rotation, scale)` as returned by [`Matrix4x4::decompose`].
+pub type Decomposition<S> = (Vec3<S>, Quaternion<S>, Vec3<S>);
+
+///Why a matrix could not be split into translation, rotation and scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecomposeError {
+    ///The matrix has a projective row, e.g. it came from `perspective`.
+    Perspective,
+    ///The scaled axes are not perpendicular to each other.
+    Shear,
+    ///At least one axis has been scaled to zero.
+    Singular,
+}
+
+impl Display for DecomposeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecomposeError::Perspective => f.write_str("matrix has a perspective component"),
+            DecomposeError::Shear => f.write_str("matrix has a shear component"),
+            DecomposeError::Singular => f.write_str("matrix has a zero scale axis"),
+        }
+    }
+}
+
+impl std::error::Error for DecomposeError {}
 
 impl<S: Scalar> SquareMatrix<S> for Matrix4x4<S> {
     const IDENTITY: Self = Matrix::from_array([
@@ -41,7 +74,7 @@ impl<S: Scalar> Matrix4x4<S> {
             Vector::from_array([S::ZERO, S::ONE, S::ZERO, v[1]]),
             Vector::from_array([S::ZERO, S::ZERO, S::ONE, v[2]]),
             Vector::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
-        ])
+        ]).conventional()
     }
 
     pub fn scale(v: Vec3<S>) -> Self {
@@ -50,27 +83,27 @@ impl<S: Scalar> Matrix4x4<S> {
             Vector::from_array([S::ZERO, v[1], S::ZERO, S::ZERO]),
             Vector::from_array([S::ZERO, S::ZERO, v[2], S::ZERO]),
             Vector::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
-        ])
+        ]).conventional()
     }
 
 
-    pub fn perspective(aspect: S, fov: Angle<S>,  near: S, far: S) -> Self {
+    ///OpenGL style right-handed perspective with depth in [-1, 1].
+    pub fn perspective(aspect: S, fov: Angle<S>, near: S, far: S) -> Self {
+        Self::perspective_with(aspect, fov, near, Some(far), ProjectionConfig::OPENGL)
+    }
 
+    ///Perspective for any clip space convention, `far` being `None` for an infinite far plane.
+    pub fn perspective_with(aspect: S, fov: Angle<S>, near: S, far: Option<S>, config: ProjectionConfig) -> Self {
+        Frustum::from_fov(aspect, fov, near, far).matrix(config)
+    }
 
-        let zero = S::ZERO;
-        let one = S::ONE;
-        let two = S::TWO;
-        let q = one / (fov.to_radians().take() / two).tangent();
-        let a = q / aspect;
-        let b = (near + far) / (near - far);
-        let c = (two * near * far) / (near - far);
+    ///OpenGL style off-center perspective.
+    pub fn frustum(left: S, right: S, bottom: S, top: S, near: S, far: S) -> Self {
+        Self::frustum_with(left, right, bottom, top, near, Some(far), ProjectionConfig::OPENGL)
+    }
 
-        Self::from_array([
-            Vector::from_array([a, zero, zero, zero]),
-            Vector::from_array([zero, q, zero, zero]),
-            Vector::from_array([zero, zero, b, zero - one]),
-            Vector::from_array([zero, zero, c, zero]),
-        ])
+    pub fn frustum_with(left: S, right: S, bottom: S, top: S, near: S, far: Option<S>, config: ProjectionConfig) -> Self {
+        Frustum { left, right, bottom, top, near, far }.matrix(config)
     }
 
     pub fn ortho(left: S, right: S, bottom: S, top: S, near: S, far: S) -> Self {
@@ -94,7 +127,7 @@ impl<S: Scalar> Matrix4x4<S> {
                 -(far + near) / (far - near),
             ]),
             Vec4::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
-        ])
+        ]).conventional()
     }
 
     pub fn look_at(eye: &Vec3<S>, center: &Vec3<S>, up: &Vec3<S>) -> Self {
@@ -106,16 +139,11 @@ impl<S: Scalar> Matrix4x4<S> {
         let u = s.cross(&f);
 
         Matrix::from_array([
-            Vector::from_array([s.x(), u.x(), -f.x(), zero]),
-            Vector::from_array([s.y(), u.y(), -f.y(), zero]),
-            Vector::from_array([s.z(), u.z(), -f.z(), zero]),
-            Vector::from_array([
-                -s.dot_product(&eye),
-                -u.dot_product(&eye),
-                f.dot_product(&eye),
-                one,
-            ]),
-        ])
+            Vector::from_array([s.x(), s.y(), s.z(), -s.dot_product(eye)]),
+            Vector::from_array([u.x(), u.y(), u.z(), -u.dot_product(eye)]),
+            Vector::from_array([-f.x(), -f.y(), -f.z(), f.dot_product(eye)]),
+            Vector::from_array([zero, zero, zero, one]),
+        ]).conventional()
     }
 
     pub fn rotation_z(angle: Angle<S>) -> Self {
@@ -127,86 +155,173 @@ impl<S: Scalar> Matrix4x4<S> {
             Vec4::from_array([sin, cos, S::ZERO, S::ZERO]),
             Vec4::from_array([S::ZERO, S::ZERO, S::ONE, S::ZERO]),
             Vec4::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
-        ])
+        ]).conventional()
     }
-}
 
-impl<S: Scalar> Mul<Self> for Matrix4x4<S> {
-    type Output = Self;
-
-    fn mul(self, b: Self) -> Self::Output {
-        let src1 = self.0;
-        let src2 = b.0;
-
-        let mut dest = Self::ZERO;
-
-        dest[0][0] = src1[0][0] * src2[0][0]
-            + src1[0][1] * src2[1][0]
-            + src1[0][2] * src2[2][0]
-            + src1[0][3] * src2[3][0];
-        dest[0][1] = src1[0][0] * src2[0][1]
-            + src1[0][1] * src2[1][1]
-            + src1[0][2] * src2[2][1]
-            + src1[0][3] * src2[3][1];
-        dest[0][2] = src1[0][0] * src2[0][2]
-            + src1[0][1] * src2[1][2]
-            + src1[0][2] * src2[2][2]
-            + src1[0][3] * src2[3][2];
-        dest[0][3] = src1[0][0] * src2[0][3]
-            + src1[0][1] * src2[1][3]
-            + src1[0][2] * src2[2][3]
-            + src1[0][3] * src2[3][3];
-        dest[1][0] = src1[1][0] * src2[0][0]
-            + src1[1][1] * src2[1][0]
-            + src1[1][2] * src2[2][0]
-            + src1[1][3] * src2[3][0];
-        dest[1][1] = src1[1][0] * src2[0][1]
-            + src1[1][1] * src2[1][1]
-            + src1[1][2] * src2[2][1]
-            + src1[1][3] * src2[3][1];
-        dest[1][2] = src1[1][0] * src2[0][2]
-            + src1[1][1] * src2[1][2]
-            + src1[1][2] * src2[2][2]
-            + src1[1][3] * src2[3][2];
-        dest[1][3] = src1[1][0] * src2[0][3]
-            + src1[1][1] * src2[1][3]
-            + src1[1][2] * src2[2][3]
-            + src1[1][3] * src2[3][3];
-        dest[2][0] = src1[2][0] * src2[0][0]
-            + src1[2][1] * src2[1][0]
-            + src1[2][2] * src2[2][0]
-            + src1[2][3] * src2[3][0];
-        dest[2][1] = src1[2][0] * src2[0][1]
-            + src1[2][1] * src2[1][1]
-            + src1[2][2] * src2[2][1]
-            + src1[2][3] * src2[3][1];
-        dest[2][2] = src1[2][0] * src2[0][2]
-            + src1[2][1] * src2[1][2]
-            + src1[2][2] * src2[2][2]
-            + src1[2][3] * src2[3][2];
-        dest[2][3] = src1[2][0] * src2[0][3]
-            + src1[2][1] * src2[1][3]
-            + src1[2][2] * src2[2][3]
-            + src1[2][3] * src2[3][3];
-        dest[3][0] = src1[3][0] * src2[0][0]
-            + src1[3][1] * src2[1][0]
-            + src1[3][2] * src2[2][0]
-            + src1[3][3] * src2[3][0];
-        dest[3][1] = src1[3][0] * src2[0][1]
-            + src1[3][1] * src2[1][1]
-            + src1[3][2] * src2[2][1]
-            + src1[3][3] * src2[3][1];
-        dest[3][2] = src1[3][0] * src2[0][2]
-            + src1[3][1] * src2[1][2]
-            + src1[3][2] * src2[2][2]
-            + src1[3][3] * src2[3][2];
-        dest[3][3] = src1[3][0] * src2[0][3]
-            + src1[3][1] * src2[1][3]
-            + src1[3][2] * src2[2][3]
-            + src1[3][3] * src2[3][3];
-
-        dest
+    pub fn rotation_x(angle: Angle<S>) -> Self {
+        let angle = angle.take_radians();
+        let (sin, cos, neg_sin) = (angle.sine(), angle.cosine(), -angle.sine());
+
+        Self::from_array([
+            Vec4::from_array([S::ONE, S::ZERO, S::ZERO, S::ZERO]),
+            Vec4::from_array([S::ZERO, cos, neg_sin, S::ZERO]),
+            Vec4::from_array([S::ZERO, sin, cos, S::ZERO]),
+            Vec4::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
+        ]).conventional()
     }
+
+    pub fn rotation_y(angle: Angle<S>) -> Self {
+        let angle = angle.take_radians();
+        let (sin, cos, neg_sin) = (angle.sine(), angle.cosine(), -angle.sine());
+
+        Self::from_array([
+            Vec4::from_array([cos, S::ZERO, sin, S::ZERO]),
+            Vec4::from_array([S::ZERO, S::ONE, S::ZERO, S::ZERO]),
+            Vec4::from_array([neg_sin, S::ZERO, cos, S::ZERO]),
+            Vec4::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
+        ]).conventional()
+    }
+
+    ///Rotation of `angle` around an arbitrary `axis` (Rodrigues' rotation formula). The axis does not need to be normalized.
+    pub fn rotation_axis(axis: Vec3<S>, angle: Angle<S>) -> Self {
+        let (x, y, z) = axis.normalized().x_y_z();
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let t = S::ONE - cos;
+
+        Self::from_array([
+            Vec4::from_array([t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y, S::ZERO]),
+            Vec4::from_array([t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x, S::ZERO]),
+            Vec4::from_array([t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos, S::ZERO]),
+            Vec4::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
+        ]).conventional()
+    }
+
+    ///Shear where each factor names the sheared axis followed by the axis it is sheared along,
+    ///e.g. `xy` adds `xy * y` to `x`.
+    pub fn shear(xy: S, xz: S, yx: S, yz: S, zx: S, zy: S) -> Self {
+        Self::from_array([
+            Vec4::from_array([S::ONE, xy, xz, S::ZERO]),
+            Vec4::from_array([yx, S::ONE, yz, S::ZERO]),
+            Vec4::from_array([zx, zy, S::ONE, S::ZERO]),
+            Vec4::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
+        ]).conventional()
+    }
+
+    ///Reflection about the plane with the given `normal` passing through `point`.
+    pub fn reflection(normal: Vec3<S>, point: Vec3<S>) -> Self {
+        let (x, y, z) = normal.normalized().x_y_z();
+        let d = normal.normalized().dot_product(&point);
+        let two = S::TWO;
+
+        Self::from_array([
+            Vec4::from_array([S::ONE - two * x * x, -two * x * y, -two * x * z, two * d * x]),
+            Vec4::from_array([-two * x * y, S::ONE - two * y * y, -two * y * z, two * d * y]),
+            Vec4::from_array([-two * x * z, -two * y * z, S::ONE - two * z * z, two * d * z]),
+            Vec4::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE]),
+        ]).conventional()
+    }
+
+    ///Pads a 3x3 matrix into the upper-left corner of an identity 4x4 matrix.
+    pub fn from_3x3(m: Matrix3x3<S>) -> Self {
+        let mut out = Self::IDENTITY;
+
+        for row in 0..3 {
+            for col in 0..3 {
+                out[row][col] = m[row][col];
+            }
+        }
+
+        out
+    }
+
+    pub fn from_quaternion(q: Quaternion<S>) -> Self {
+        q.to_rotation_4x4()
+    }
+
+    ///Transforms a point, including translation and the perspective divide.
+    pub fn transform_point(&self, point: Vec3<S>) -> Vec3<S> {
+        self.transform(point.to_point()).perspective_divide()
+    }
+
+    ///Transforms a direction, ignoring translation.
+    pub fn transform_vector(&self, vector: Vec3<S>) -> Vec3<S> {
+        self.transform(vector.to_direction()).down()
+    }
+
+    ///Transforms a surface normal by the inverse-transpose, so it stays perpendicular under non-uniform scale.
+    ///The result is normalized, `None` when the matrix is singular.
+    pub fn transform_normal(&self, normal: Vec3<S>) -> Option<Vec3<S>> {
+        let inverse_transpose = self.inverse()?.transpose();
+        Some(inverse_transpose.transform_vector(normal).normalized())
+    }
+
+    ///Splits an affine transform into the `(translation, rotation, scale)` that [`Matrix4x4::from_trs`] would rebuild it from.
+    ///A reflection is reported as a negative x scale.
+    pub fn try_decompose(&self) -> Result<Decomposition<S>, DecomposeError> {
+        let epsilon = S::from_f64(1e-4);
+        let mut m = self.conventional();
+
+        if m[3][0].s_abs() > epsilon || m[3][1].s_abs() > epsilon || m[3][2].s_abs() > epsilon {
+            return Err(DecomposeError::Perspective);
+        }
+        if m[3][3].s_abs() < epsilon {
+            return Err(DecomposeError::Singular);
+        }
+        if m[3][3] != S::ONE {
+            let w = m[3][3];
+            for row in 0..4 {
+                m[row] = m[row] * (S::ONE / w);
+            }
+        }
+
+        let translation = Vec3::from_array([m[0][3], m[1][3], m[2][3]]);
+        let mut axes = [0, 1, 2].map(|col| Vec3::from_array([m[0][col], m[1][col], m[2][col]]));
+        let mut scale = Vec3::from_array(axes.map(|axis| axis.magnitude()));
+
+        for i in 0..3 {
+            if scale[i] < epsilon {
+                return Err(DecomposeError::Singular);
+            }
+            axes[i] = axes[i] * (S::ONE / scale[i]);
+        }
+
+        if axes[0].dot_product(&axes[1]).s_abs() > epsilon
+            || axes[0].dot_product(&axes[2]).s_abs() > epsilon
+            || axes[1].dot_product(&axes[2]).s_abs() > epsilon
+        {
+            return Err(DecomposeError::Shear);
+        }
+
+        if axes[0].cross(&axes[1]).dot_product(&axes[2]) < S::ZERO {
+            scale[0] = -scale[0];
+            axes[0] = -axes[0];
+        }
+
+        let rotation = Matrix3x3::from_array([
+            Vec3::from_array([axes[0][0], axes[1][0], axes[2][0]]),
+            Vec3::from_array([axes[0][1], axes[1][1], axes[2][1]]),
+            Vec3::from_array([axes[0][2], axes[1][2], axes[2][2]]),
+        ]);
+
+        Ok((translation, Quaternion::from_rotation_matrix(rotation.conventional()), scale))
+    }
+
+    pub fn decompose(&self) -> Option<Decomposition<S>> {
+        self.try_decompose().ok()
+    }
+
+    ///Builds a transform that scales first, then rotates, then translates.
+    pub fn from_trs(translation: Vec3<S>, rotation: Quaternion<S>, scale: Vec3<S>) -> Self {
+        Self::scale(scale)
+            .then(Self::from_quaternion(rotation))
+            .then(Self::translation(translation))
+    }
+}
+
+///Lua passes viewports as `(x, y, width, height)` plus the config the projection was built with,
+///OpenGL when left out.
+fn lua_viewport<S: Scalar>(v: Vec4<S>, clip: Option<ProjectionConfig>) -> Viewport<S> {
+    Viewport::new(v[0], v[1], v[2], v[3]).with_clip(clip.unwrap_or(ProjectionConfig::OPENGL))
 }
 
 crate::lua_matrix!(Matrix4x4F => f32 {
@@ -263,6 +378,18 @@ crate::lua_matrix!(Matrix4x4F => f32 {
             Ok(Self::perspective(aspect_ratio, angle, near, far))
         }
 
+        fn mat4x4f_frustum(args: (f32, f32, f32, f32, f32, f32)) {
+            Ok(Self::frustum(args.0, args.1, args.2, args.3, args.4, args.5))
+        }
+
+        fn mat4x4f_perspective_with(args: (f32, Angle<f32>, f32, Option<f32>, ProjectionConfig)) {
+            Ok(Self::perspective_with(args.0, args.1, args.2, args.3, args.4))
+        }
+
+        fn mat4x4f_frustum_with(args: (f32, f32, f32, f32, f32, Option<f32>, ProjectionConfig)) {
+            Ok(Self::frustum_with(args.0, args.1, args.2, args.3, args.4, args.5, args.6))
+        }
+
         fn mat4x4f_look_at(args: (Option<Vec3F>, Option<Vec3F>, Option<Vec3F>)) {
             let eye = args.0.unwrap_or(Vec3F::ZERO);
             let center = args.1.unwrap_or(Vec3F::ZERO);
@@ -270,13 +397,360 @@ crate::lua_matrix!(Matrix4x4F => f32 {
 
             Ok(Self::look_at(&eye, &center, &up))
         }
+
+        fn mat4x4f_project(args: (Vec3F, Self, Self, Vec4F, Option<ProjectionConfig>)) {
+            Ok(crate::matrix::projection::project(args.0, &args.1, &args.2, &lua_viewport(args.3, args.4)))
+        }
+
+        fn mat4x4f_unproject(args: (Vec3F, Self, Self, Vec4F, Option<ProjectionConfig>)) {
+            Ok(crate::matrix::projection::unproject(args.0, &args.1, &args.2, &lua_viewport(args.3, args.4)))
+        }
+
+        fn mat4x4f_screen_ray(args: (Vec2F, Self, Self, Vec4F, Option<ProjectionConfig>)) {
+            let ray = crate::matrix::projection::screen_ray(args.0, &args.1, &args.2, &lua_viewport(args.3, args.4));
+            Ok(match ray {
+                Some(ray) => (Some(ray.origin), Some(ray.direction)),
+                None => (None, None),
+            })
+        }
+
+        fn mat4x4f_rotation_x(angle: AngleF) {
+            Ok(Self::rotation_x(angle))
+        }
+
+        fn mat4x4f_rotation_y(angle: AngleF) {
+            Ok(Self::rotation_y(angle))
+        }
+
+        fn mat4x4f_rotation_z(angle: AngleF) {
+            Ok(Self::rotation_z(angle))
+        }
+
+        fn mat4x4f_rotation_axis(args: (Vec3F, AngleF)) {
+            Ok(Self::rotation_axis(args.0, args.1))
+        }
+
+        fn mat4x4f_shear(args: (Option<f32>, Option<f32>, Option<f32>, Option<f32>, Option<f32>, Option<f32>)) {
+            Ok(Self::shear(
+                args.0.unwrap_or(0f32),
+                args.1.unwrap_or(0f32),
+                args.2.unwrap_or(0f32),
+                args.3.unwrap_or(0f32),
+                args.4.unwrap_or(0f32),
+                args.5.unwrap_or(0f32)
+            ))
+        }
+
+        fn mat4x4f_reflection(args: (Vec3F, Option<Vec3F>)) {
+            Ok(Self::reflection(args.0, args.1.unwrap_or(Vec3F::ZERO)))
+        }
+
+        fn mat4x4f_from_mat3x3(m: Matrix3x3F) {
+            Ok(Self::from_3x3(m))
+        }
+
+        fn mat4x4f_from_quaternion(q: QuatF) {
+            Ok(Self::from_quaternion(q))
+        }
+
+        fn mat4x4f_from_trs(args: (Option<Vec3F>, Option<QuatF>, Option<Vec3F>)) {
+            Ok(Self::from_trs(
+                args.0.unwrap_or(Vec3F::ZERO),
+                args.1.unwrap_or(QuatF::IDENTITY),
+                args.2.unwrap_or(Vec3F::ONE)
+            ))
+        }
     ]
     methods = {
+        fn power(this, n: i32) {
+            Ok(this.power(n))
+        }
+
+        fn exp(this, _args: ()) {
+            Ok(this.exp())
+        }
+
+        fn ln(this, _args: ()) {
+            Ok(this.ln())
+        }
 
+        fn sqrt(this, _args: ()) {
+            Ok(this.sqrt())
+        }
+
+        fn then(this, next: Self) {
+            Ok(this.then(next))
+        }
+
+        fn transform_point(this, point: Vec3F) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec3F) {
+            Ok(this.transform_vector(vector))
+        }
+
+        fn transform_normal(this, normal: Vec3F) {
+            Ok(this.transform_normal(normal))
+        }
+
+        fn inverse(this, _args: ()) {
+            Ok(this.inverse())
+        }
+
+        fn determinant(this, _args: ()) {
+            Ok(this.determinant())
+        }
+
+        fn decompose(this, _args: ()) {
+            this.try_decompose().map_err(|e| rlua::Error::RuntimeError(e.to_string()))
+        }
+    }
+    mul = { Self, Matrix4x3F, Vec4F }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix4x4D => f64 {
+    Args = (Option<Vec4D>, Option<Vec4D>, Option<Vec4D>, Option<Vec4D>),
+    CONSTRUCTOR_NAME = "mat4x4d",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec4D::ZERO),
+                args.1.unwrap_or(Vec4D::ZERO),
+                args.2.unwrap_or(Vec4D::ZERO),
+                args.3.unwrap_or(Vec4D::ZERO)
+            ]))
+        )
+    }
+    associated_functions = (lua) [
+        fn mat4x4d_identity(arg: Option<f64>) {
+            Ok(Self::identity(arg))
+        }
+
+        fn mat4x4d_translation(arg: Vec3D) {
+            Ok(Self::translation(arg))
+        }
+
+        fn mat4x4d_scale(arg: Vec3D) {
+            Ok(Self::scale(arg))
+        }
+
+        fn mat4x4d_ortho(args: (Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>)) {
+            let left = args.0.unwrap_or(0f64);
+            let right = args.1.unwrap_or(1f64);
+            let bottom = args.2.unwrap_or(0f64);
+            let top = args.3.unwrap_or(1f64);
+            let near = args.4.unwrap_or(0f64);
+            let far = args.5.unwrap_or(1f64);
+
+            Ok(Self::ortho(left, right, bottom, top, near, far))
+        }
+
+        fn mat4x4d_perspective(args: (Option<f64>, Option<AngleD>, Option<f64>, Option<f64>)) {
+            let aspect_ratio = args.0.unwrap_or(1f64);
+            let angle = args.1.unwrap_or(Angle::Degrees(90f64));
+            let near = args.2.unwrap_or(0.01f64);
+            let far = args.3.unwrap_or(100f64);
+
+            Ok(Self::perspective(aspect_ratio, angle, near, far))
+        }
+
+        fn mat4x4d_frustum(args: (f64, f64, f64, f64, f64, f64)) {
+            Ok(Self::frustum(args.0, args.1, args.2, args.3, args.4, args.5))
+        }
+
+        fn mat4x4d_perspective_with(args: (f64, AngleD, f64, Option<f64>, ProjectionConfig)) {
+            Ok(Self::perspective_with(args.0, args.1, args.2, args.3, args.4))
+        }
+
+        fn mat4x4d_frustum_with(args: (f64, f64, f64, f64, f64, Option<f64>, ProjectionConfig)) {
+            Ok(Self::frustum_with(args.0, args.1, args.2, args.3, args.4, args.5, args.6))
+        }
+
+        fn mat4x4d_look_at(args: (Option<Vec3D>, Option<Vec3D>, Option<Vec3D>)) {
+            let eye = args.0.unwrap_or(Vec3D::ZERO);
+            let center = args.1.unwrap_or(Vec3D::ZERO);
+            let up = args.2.unwrap_or(Vec3D::ZERO);
+
+            Ok(Self::look_at(&eye, &center, &up))
+        }
+
+        fn mat4x4d_project(args: (Vec3D, Self, Self, Vec4D, Option<ProjectionConfig>)) {
+            Ok(crate::matrix::projection::project(args.0, &args.1, &args.2, &lua_viewport(args.3, args.4)))
+        }
+
+        fn mat4x4d_unproject(args: (Vec3D, Self, Self, Vec4D, Option<ProjectionConfig>)) {
+            Ok(crate::matrix::projection::unproject(args.0, &args.1, &args.2, &lua_viewport(args.3, args.4)))
+        }
+
+        fn mat4x4d_screen_ray(args: (Vec2D, Self, Self, Vec4D, Option<ProjectionConfig>)) {
+            let ray = crate::matrix::projection::screen_ray(args.0, &args.1, &args.2, &lua_viewport(args.3, args.4));
+            Ok(match ray {
+                Some(ray) => (Some(ray.origin), Some(ray.direction)),
+                None => (None, None),
+            })
+        }
+
+        fn mat4x4d_rotation_x(angle: AngleD) {
+            Ok(Self::rotation_x(angle))
+        }
+
+        fn mat4x4d_rotation_y(angle: AngleD) {
+            Ok(Self::rotation_y(angle))
+        }
+
+        fn mat4x4d_rotation_z(angle: AngleD) {
+            Ok(Self::rotation_z(angle))
+        }
+
+        fn mat4x4d_rotation_axis(args: (Vec3D, AngleD)) {
+            Ok(Self::rotation_axis(args.0, args.1))
+        }
+
+        fn mat4x4d_shear(args: (Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>)) {
+            Ok(Self::shear(
+                args.0.unwrap_or(0f64),
+                args.1.unwrap_or(0f64),
+                args.2.unwrap_or(0f64),
+                args.3.unwrap_or(0f64),
+                args.4.unwrap_or(0f64),
+                args.5.unwrap_or(0f64)
+            ))
+        }
+
+        fn mat4x4d_reflection(args: (Vec3D, Option<Vec3D>)) {
+            Ok(Self::reflection(args.0, args.1.unwrap_or(Vec3D::ZERO)))
+        }
+
+        fn mat4x4d_from_mat3x3(m: Matrix3x3D) {
+            Ok(Self::from_3x3(m))
+        }
+
+        fn mat4x4d_from_quaternion(q: QuatD) {
+            Ok(Self::from_quaternion(q))
+        }
+
+        fn mat4x4d_from_trs(args: (Option<Vec3D>, Option<QuatD>, Option<Vec3D>)) {
+            Ok(Self::from_trs(
+                args.0.unwrap_or(Vec3D::ZERO),
+                args.1.unwrap_or(QuatD::IDENTITY),
+                args.2.unwrap_or(Vec3D::ONE)
+            ))
+        }
+    ]
+    methods = {
+        fn power(this, n: i32) {
+            Ok(this.power(n))
+        }
+
+        fn exp(this, _args: ()) {
+            Ok(this.exp())
+        }
+
+        fn ln(this, _args: ()) {
+            Ok(this.ln())
+        }
+
+        fn sqrt(this, _args: ()) {
+            Ok(this.sqrt())
+        }
+
+        fn then(this, next: Self) {
+            Ok(this.then(next))
+        }
+
+        fn transform_point(this, point: Vec3D) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec3D) {
+            Ok(this.transform_vector(vector))
+        }
+
+        fn transform_normal(this, normal: Vec3D) {
+            Ok(this.transform_normal(normal))
+        }
+
+        fn inverse(this, _args: ()) {
+            Ok(this.inverse())
+        }
+
+        fn determinant(this, _args: ()) {
+            Ok(this.determinant())
+        }
+
+        fn decompose(this, _args: ()) {
+            this.try_decompose().map_err(|e| rlua::Error::RuntimeError(e.to_string()))
+        }
+    }
+    mul = { Self, Matrix4x3D, Vec4D }
+    meta_method = {}
+});
+
+crate::lua_matrix!(Matrix4x4X => Q16_16 {
+    Args = (Option<Vec4X>, Option<Vec4X>, Option<Vec4X>, Option<Vec4X>),
+    CONSTRUCTOR_NAME = "mat4x4x",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args |
+            Ok(Self::from_array([
+                args.0.unwrap_or(Vec4X::ZERO),
+                args.1.unwrap_or(Vec4X::ZERO),
+                args.2.unwrap_or(Vec4X::ZERO),
+                args.3.unwrap_or(Vec4X::ZERO)
+            ]))
+        )
     }
-    meta_method = {
-        MetaMethod::Mul[this] => b: Self {
-            Ok(*this * b)
+    associated_functions = (lua) [
+        fn mat4x4x_identity(arg: Option<Q16_16>) {
+            Ok(Self::identity(arg))
+        }
+
+        fn mat4x4x_translation(arg: Vec3X) {
+            Ok(Self::translation(arg))
+        }
+
+        fn mat4x4x_scale(arg: Vec3X) {
+            Ok(Self::scale(arg))
+        }
+
+        fn mat4x4x_rotation_axis(args: (Vec3X, AngleX)) {
+            Ok(Self::rotation_axis(args.0, args.1))
+        }
+
+        fn mat4x4x_from_quaternion(q: QuatX) {
+            Ok(Self::from_quaternion(q))
+        }
+
+        fn mat4x4x_from_trs(args: (Option<Vec3X>, Option<QuatX>, Option<Vec3X>)) {
+            Ok(Self::from_trs(
+                args.0.unwrap_or(Vec3X::ZERO),
+                args.1.unwrap_or(QuatX::IDENTITY),
+                args.2.unwrap_or(Vec3X::ONE)
+            ))
+        }
+    ]
+    methods = {
+        fn then(this, next: Self) {
+            Ok(this.then(next))
+        }
+
+        fn transform_point(this, point: Vec3X) {
+            Ok(this.transform_point(point))
+        }
+
+        fn transform_vector(this, vector: Vec3X) {
+            Ok(this.transform_vector(vector))
+        }
+
+        fn inverse(this, _args: ()) {
+            Ok(this.inverse())
+        }
+
+        fn determinant(this, _args: ()) {
+            Ok(this.determinant())
         }
     }
+    mul = { Self, Vec4X }
+    meta_method = {}
 });
@@ -3,10 +3,19 @@ use crate::scalar::Scalar;
 use crate::vector::Vector;
 
 pub mod imp;
+pub(crate) mod algorithms;
+mod functions;
+pub mod convention;
+pub mod dmatrix;
 pub mod square_matrix;
 pub mod matrix2x2;
+pub mod matrix2x3;
+pub mod matrix3x2;
 pub mod matrix3x3;
+pub mod matrix3x4;
+pub mod matrix4x3;
 pub mod matrix4x4;
+pub mod projection;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, FromLua)]
 #[repr(C)]
@@ -0,0 +1,266 @@
+use crate::angle::Angle;
+use crate::matrix::matrix4x4::Matrix4x4;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::scalar::Scalar;
+use crate::vector::vec2::Vec2;
+use crate::vector::vec3::Vec3;
+use crate::vector::vec4::Vec4;
+use rlua::{FromLua, Lua};
+
+///The range normalized device depth is mapped into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthRange {
+    ///OpenGL's default, near maps to -1 and far to 1.
+    NegativeOneToOne,
+    ///Vulkan, Direct3D and Metal, near maps to 0 and far to 1.
+    ZeroToOne,
+}
+
+///Which way the camera looks in view space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handedness {
+    ///The camera looks down -Z.
+    Right,
+    ///The camera looks down +Z.
+    Left,
+}
+
+///Clip space conventions a projection matrix is built for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProjectionConfig {
+    pub depth_range: DepthRange,
+    pub handedness: Handedness,
+    ///Swaps near and far in the depth range, which spreads floating point depth precision more evenly.
+    pub reversed_z: bool,
+}
+
+impl ProjectionConfig {
+    pub const OPENGL: Self = Self {
+        depth_range: DepthRange::NegativeOneToOne,
+        handedness: Handedness::Right,
+        reversed_z: false,
+    };
+
+    pub const VULKAN: Self = Self {
+        depth_range: DepthRange::ZeroToOne,
+        handedness: Handedness::Right,
+        reversed_z: false,
+    };
+
+    pub const DIRECTX: Self = Self {
+        depth_range: DepthRange::ZeroToOne,
+        handedness: Handedness::Left,
+        reversed_z: false,
+    };
+
+    pub const fn reversed(self) -> Self {
+        Self {
+            reversed_z: !self.reversed_z,
+            ..self
+        }
+    }
+
+    ///The normalized depth of the near and far planes.
+    pub fn depth_bounds<S: Scalar>(self) -> (S, S) {
+        let (near, far) = match self.depth_range {
+            DepthRange::NegativeOneToOne => (S::NEG_ONE, S::ONE),
+            DepthRange::ZeroToOne => (S::ZERO, S::ONE),
+        };
+
+        match self.reversed_z {
+            true => (far, near),
+            false => (near, far),
+        }
+    }
+
+    ///The sign of view space z in front of the camera.
+    fn forward<S: Scalar>(self) -> S {
+        match self.handedness {
+            Handedness::Right => S::NEG_ONE,
+            Handedness::Left => S::ONE,
+        }
+    }
+}
+
+///Lua names a config `"opengl"`, `"vulkan"` or `"directx"`, with a `"_reversed"` suffix for reversed-Z.
+impl<'lua> FromLua<'lua> for ProjectionConfig {
+    fn from_lua(value: rlua::Value<'lua>, lua: &'lua Lua) -> rlua::Result<Self> {
+        let name = String::from_lua(value, lua)?.to_lowercase();
+        let (base, reversed) = match name.strip_suffix("_reversed") {
+            Some(base) => (base, true),
+            None => (name.as_str(), false),
+        };
+        let config = match base {
+            "opengl" => Self::OPENGL,
+            "vulkan" => Self::VULKAN,
+            "directx" => Self::DIRECTX,
+            _ => return Err(rlua::Error::RuntimeError(format!("unknown projection config {name}"))),
+        };
+        Ok(crate::ternary!(reversed => config.reversed() ; config))
+    }
+}
+
+///A perspective view volume, `far` being `None` for an infinitely distant far plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum<S: Scalar> {
+    pub left: S,
+    pub right: S,
+    pub bottom: S,
+    pub top: S,
+    pub near: S,
+    pub far: Option<S>,
+}
+
+impl<S: Scalar> Frustum<S> {
+    ///A symmetric frustum from a vertical field of view.
+    pub fn from_fov(aspect: S, fov: Angle<S>, near: S, far: Option<S>) -> Self {
+        let top = near * (fov.take_radians() / S::TWO).tangent();
+        let right = top * aspect;
+
+        Self {
+            left: -right,
+            right,
+            bottom: -top,
+            top,
+            near,
+            far,
+        }
+    }
+
+    ///Depth is mapped as `z_ndc = a * forward + b / w`, where `forward` is the sign of view space z in front
+    ///of the camera and `w` is the distance in front of the camera.
+    fn depth_coefficients(&self, config: ProjectionConfig) -> (S, S) {
+        let (low, high) = config.depth_bounds::<S>();
+        let forward = config.forward::<S>();
+        let near = self.near;
+
+        match self.far {
+            Some(far) => (
+                forward * (high * far - low * near) / (far - near),
+                (low - high) * near * far / (far - near),
+            ),
+            None => (forward * high, (low - high) * near),
+        }
+    }
+
+    pub fn matrix(&self, config: ProjectionConfig) -> Matrix4x4<S> {
+        let (zero, two) = (S::ZERO, S::TWO);
+        let Self { left, right, bottom, top, near, .. } = *self;
+        let forward = config.forward::<S>();
+        let (a, b) = self.depth_coefficients(config);
+
+        Matrix::from_array([
+            Vec4::from_array([two * near / (right - left), zero, -forward * (right + left) / (right - left), zero]),
+            Vec4::from_array([zero, two * near / (top - bottom), -forward * (top + bottom) / (top - bottom), zero]),
+            Vec4::from_array([zero, zero, a, b]),
+            Vec4::from_array([zero, zero, forward, zero]),
+        ])
+        .conventional()
+    }
+
+    ///Inverse of [`Frustum::matrix`] for the same `config`, taking a normalized device coordinate back to view space.
+    pub fn unproject(&self, ndc: Vec3<S>, config: ProjectionConfig) -> Vec3<S> {
+        let Self { left, right, bottom, top, near, .. } = *self;
+        let forward = config.forward::<S>();
+        let (a, b) = self.depth_coefficients(config);
+
+        let distance = b / (ndc[2] - a * forward);
+        let x = (ndc[0] * (right - left) + (right + left)) * distance / (S::TWO * near);
+        let y = (ndc[1] * (top - bottom) + (top + bottom)) * distance / (S::TWO * near);
+
+        Vec3::from_array([x, y, forward * distance])
+    }
+}
+
+///The window rectangle normalized device coordinates are mapped to, plus the clip space convention
+///of the projection in use. Window depth is normalized device depth mapped linearly into [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport<S: Scalar> {
+    pub x: S,
+    pub y: S,
+    pub width: S,
+    pub height: S,
+    pub clip: ProjectionConfig,
+}
+
+impl<S: Scalar> Viewport<S> {
+    pub fn new(x: S, y: S, width: S, height: S) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            clip: ProjectionConfig::OPENGL,
+        }
+    }
+
+    pub fn with_clip(self, clip: ProjectionConfig) -> Self {
+        Self { clip, ..self }
+    }
+
+    fn ndc_to_window_depth(&self, z: S) -> S {
+        match self.clip.depth_range {
+            DepthRange::NegativeOneToOne => (z + S::ONE) / S::TWO,
+            DepthRange::ZeroToOne => z,
+        }
+    }
+
+    fn window_to_ndc_depth(&self, z: S) -> S {
+        match self.clip.depth_range {
+            DepthRange::NegativeOneToOne => z * S::TWO - S::ONE,
+            DepthRange::ZeroToOne => z,
+        }
+    }
+}
+
+///Maps a world space point to window coordinates, `z` being its window depth.
+pub fn project<S: Scalar>(point: Vec3<S>, view: &Matrix4x4<S>, projection: &Matrix4x4<S>, viewport: &Viewport<S>) -> Vec3<S> {
+    let ndc = view.then(*projection).transform_point(point);
+
+    Vec3::from_array([
+        viewport.x + (ndc[0] + S::ONE) / S::TWO * viewport.width,
+        viewport.y + (ndc[1] + S::ONE) / S::TWO * viewport.height,
+        viewport.ndc_to_window_depth(ndc[2]),
+    ])
+}
+
+///Inverse of [`project`], `None` when the view-projection matrix cannot be inverted.
+pub fn unproject<S: Scalar>(
+    screen_point: Vec3<S>,
+    view: &Matrix4x4<S>,
+    projection: &Matrix4x4<S>,
+    viewport: &Viewport<S>,
+) -> Option<Vec3<S>> {
+    let inverse = view.then(*projection).inverse()?;
+
+    let ndc = Vec3::from_array([
+        (screen_point[0] - viewport.x) / viewport.width * S::TWO - S::ONE,
+        (screen_point[1] - viewport.y) / viewport.height * S::TWO - S::ONE,
+        viewport.window_to_ndc_depth(screen_point[2]),
+    ]);
+
+    Some(inverse.transform_point(ndc))
+}
+
+///The world space ray under a window position, starting on the near plane. Works for infinite far planes
+///since only the near plane and a point halfway through the depth range are unprojected.
+pub fn screen_ray<S: Scalar>(
+    cursor: Vec2<S>,
+    view: &Matrix4x4<S>,
+    projection: &Matrix4x4<S>,
+    viewport: &Viewport<S>,
+) -> Option<Ray<S>> {
+    let (near, far) = viewport.clip.depth_bounds::<S>();
+    let (near, far) = (viewport.ndc_to_window_depth(near), viewport.ndc_to_window_depth(far));
+
+    let origin = unproject(Vec3::from_array([cursor[0], cursor[1], near]), view, projection, viewport)?;
+    let toward = unproject(
+        Vec3::from_array([cursor[0], cursor[1], (near + far) / S::TWO]),
+        view,
+        projection,
+        viewport,
+    )?;
+
+    Some(Ray::new(origin, toward - origin))
+}
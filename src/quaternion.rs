@@ -1,4 +1,7 @@
+use crate::angle::Angle;
 use crate::euler::Euler;
+use crate::fixed::Q16_16;
+use crate::matrix::matrix3x3::RotationOrder;
 use crate::matrix::Matrix;
 use crate::prelude::vec3;
 use crate::scalar::Scalar;
@@ -6,10 +9,12 @@ use crate::vector::vec3::Vec3;
 use crate::vector::Vector;
 use rlua::{AnyUserData, Integer, MetaMethod, Number, UserData, UserDataMethods, Lua};
 use mlua::FromLua;
-use std::ops::{Add, Mul};
+use crate::interpolation::Interpolation;
+use std::ops::{Add, Mul, Neg};
 
 pub type QuatF = Quaternion<f32>;
 pub type QuatD = Quaternion<f64>;
+pub type QuatX = Quaternion<Q16_16>;
 
 #[derive(Debug, Clone, Copy, PartialEq, FromLua)]
 pub struct Quaternion<S: Scalar> {
@@ -23,23 +28,111 @@ impl<S: Scalar> Quaternion<S> {
         xyz: Vec3::ZERO,
     };
 
+    ///Rotation for `euler`, composed in its stored [`RotationOrder`].
     pub fn from_euler(euler: Euler<S>) -> Self {
-        let (mut roll, mut pitch, mut yaw) = euler.to_radians().take();
+        Self::from_euler_with_order(euler, euler.order)
+    }
+
+    ///Rotation by `angle` about `axis`, which does not need to be normalized.
+    pub fn from_axis_angle(axis: Vec3<S>, angle: Angle<S>) -> Self {
+        let half = angle.take_radians() / S::TWO;
+        Self {
+            w: half.cosine(),
+            xyz: axis.normalized() * half.sine(),
+        }
+    }
+
+    ///Shortest rotation turning the direction `from` onto the direction `to`.
+    pub fn from_rotation_arc(from: Vec3<S>, to: Vec3<S>) -> Self {
+        let (from, to) = (from.normalized(), to.normalized());
+        let dot = from.dot_product(&to);
+
+        //Opposite directions, any axis perpendicular to `from` will do.
+        if dot < S::from_f64(1e-6) - S::ONE {
+            let mut axis = from.cross(&vec3(S::ONE, S::ZERO, S::ZERO));
+            if axis.length() < S::from_f64(1e-6) {
+                axis = from.cross(&vec3(S::ZERO, S::ONE, S::ZERO));
+            }
+            return Self::from_axis_angle(axis, Angle::Radians(S::PI));
+        }
 
-        roll /= S::TWO;
-        pitch /= S::TWO;
-        yaw /= S::TWO;
+        Self { w: S::ONE + dot, xyz: from.cross(&to) }.normalized()
+    }
 
-        let (cr, sr) = (roll.cosine(), roll.sine());
-        let (cp, sp) = (pitch.cosine(), pitch.sine());
-        let (cy, sy) = (yaw.cosine(), yaw.sine());
+    ///Orientation whose forward axis, `-Z` as in [`Matrix::look_at`](crate::matrix::matrix4x4::Matrix4x4::look_at),
+    ///points along `forward` and whose `+Y` leans towards `up`. When `up` is parallel to `forward`, another
+    ///up axis is picked.
+    pub fn look_rotation(forward: Vec3<S>, up: Vec3<S>) -> Self {
+        let f = forward.normalized();
+
+        //`up` gives no sideways direction, any axis not along `forward` will do.
+        let mut r = f.cross(&up);
+        if r.length() < S::from_f64(1e-6) {
+            r = f.cross(&vec3(S::ONE, S::ZERO, S::ZERO));
+            if r.length() < S::from_f64(1e-6) {
+                r = f.cross(&vec3(S::ZERO, S::ONE, S::ZERO));
+            }
+        }
+        let r = r.normalized();
+        let u = r.cross(&f);
+
+        Self::from_rotation_matrix(Matrix::from_array([
+            vec3(r.x(), u.x(), -f.x()),
+            vec3(r.y(), u.y(), -f.y()),
+            vec3(r.z(), u.z(), -f.z()),
+        ]).conventional())
+    }
 
-        let w = cr * cp * cy + sr * sp * sy;
-        let x = sr * cp * cy - cr * sp * sy;
-        let y = cr * sp * cy + sr * cp * sy;
-        let z = cr * cp * sy - sr * sp * cy;
+    ///Axis and angle in `[0, 2π]` of this rotation. The identity returns the `X` axis.
+    pub fn to_axis_angle(self) -> (Vec3<S>, Angle<S>) {
+        let q = self.normalized();
+        let angle = q.w.s_clamp(S::NEG_ONE, S::ONE).inverse_cosine() * S::TWO;
+        let sin = (S::ONE - q.w.squared()).s_clamp(S::ZERO, S::ONE).square_root2();
 
-        Quaternion { w, xyz: vec3(x, y, z) }
+        match sin < S::from_f64(1e-6) {
+            true => (vec3(S::ONE, S::ZERO, S::ZERO), Angle::Radians(angle)),
+            false => (q.xyz * (S::ONE / sin), Angle::Radians(angle)),
+        }
+    }
+
+    ///Rotation for `euler` composed in `rotation_order`, see [`RotationOrder`].
+    pub fn from_euler_with_order(euler: Euler<S>, rotation_order: RotationOrder) -> Self {
+        let x = Self::from_axis_angle(vec3(S::ONE, S::ZERO, S::ZERO), euler.roll);
+        let y = Self::from_axis_angle(vec3(S::ZERO, S::ONE, S::ZERO), euler.pitch);
+        let z = Self::from_axis_angle(vec3(S::ZERO, S::ZERO, S::ONE), euler.yaw);
+
+        match rotation_order {
+            RotationOrder::ZYX => z * y * x,
+            RotationOrder::XYZ => x * y * z,
+        }
+    }
+
+    ///Euler angles in radians for this rotation, see [`Matrix::to_euler`].
+    pub fn to_euler(self, rotation_order: RotationOrder) -> Euler<S> {
+        self.normalized().to_rotation_3x3().to_euler(rotation_order)
+    }
+
+    ///Extracts the rotation from a pure rotation matrix built in the crate's convention.
+    pub fn from_rotation_matrix(m: Matrix<3, 3, S>) -> Self {
+        let r = m.conventional();
+        let (one, two, four) = (S::ONE, S::TWO, S::FOUR);
+        let trace = r[0][0] + r[1][1] + r[2][2];
+
+        let (w, x, y, z) = if trace > S::ZERO {
+            let s = (trace + one).square_root2() * two;
+            (s / four, (r[2][1] - r[1][2]) / s, (r[0][2] - r[2][0]) / s, (r[1][0] - r[0][1]) / s)
+        } else if r[0][0] > r[1][1] && r[0][0] > r[2][2] {
+            let s = (one + r[0][0] - r[1][1] - r[2][2]).square_root2() * two;
+            ((r[2][1] - r[1][2]) / s, s / four, (r[0][1] + r[1][0]) / s, (r[0][2] + r[2][0]) / s)
+        } else if r[1][1] > r[2][2] {
+            let s = (one + r[1][1] - r[0][0] - r[2][2]).square_root2() * two;
+            ((r[0][2] - r[2][0]) / s, (r[0][1] + r[1][0]) / s, s / four, (r[1][2] + r[2][1]) / s)
+        } else {
+            let s = (one + r[2][2] - r[0][0] - r[1][1]).square_root2() * two;
+            ((r[1][0] - r[0][1]) / s, (r[0][2] + r[2][0]) / s, (r[1][2] + r[2][1]) / s, s / four)
+        };
+
+        Self { w, xyz: vec3(x, y, z) }
     }
 
     pub fn pure(xyz: Vec3<S>) -> Self {
@@ -61,6 +154,184 @@ impl<S: Scalar> Quaternion<S> {
         self.conjugate() * (S::ONE / self.magnitude().squared())
     }
 
+    pub fn dot(self, other: Self) -> S {
+        self.w * other.w + self.xyz.dot_product(&other.xyz)
+    }
+
+    pub fn normalized(self) -> Self {
+        self * (S::ONE / self.magnitude())
+    }
+
+    pub fn normalize(&mut self) {
+        *self = self.normalized();
+    }
+
+    pub fn is_normalized(self) -> bool {
+        (self.magnitude() - S::ONE).s_abs() < S::from_f64(1e-4)
+    }
+
+    ///Angle of the smallest rotation taking the orientation `self` to `other`.
+    pub fn angle_to(self, other: Self) -> Angle<S> {
+        let dot = self.normalized().dot(other.normalized()).s_abs().s_clamp(S::ZERO, S::ONE);
+        Angle::Radians(dot.inverse_cosine() * S::TWO)
+    }
+
+    ///`v` rotated by this quaternion, the same as `self * v`.
+    pub fn rotate(self, v: Vec3<S>) -> Vec3<S> {
+        self * v
+    }
+
+    ///[`Vec3::FORWARD`] rotated into this orientation.
+    pub fn forward(self) -> Vec3<S> {
+        self * Vec3::FORWARD
+    }
+
+    ///[`Vec3::RIGHT`] rotated into this orientation.
+    pub fn right(self) -> Vec3<S> {
+        self * Vec3::RIGHT
+    }
+
+    ///[`Vec3::UP`] rotated into this orientation.
+    pub fn up(self) -> Vec3<S> {
+        self * Vec3::UP
+    }
+
+    ///`e^q`, for a pure quaternion `(0, θ·axis)` this is the rotation by `2θ` about `axis`.
+    pub fn exp(self) -> Self {
+        let theta = self.xyz.magnitude();
+        let scale = self.w.exponential();
+        let xyz = crate::ternary!(theta == S::ZERO => Vec3::ZERO ; self.xyz * (theta.sine() / theta));
+
+        Self { w: theta.cosine(), xyz } * scale
+    }
+
+    ///Natural logarithm, the inverse of [`Quaternion::exp`].
+    pub fn ln(self) -> Self {
+        let magnitude = self.magnitude();
+        let theta = self.xyz.magnitude();
+        let xyz = match theta == S::ZERO {
+            true => Vec3::ZERO,
+            false => self.xyz * ((self.w / magnitude).s_clamp(S::NEG_ONE, S::ONE).inverse_cosine() / theta),
+        };
+
+        Self { w: magnitude.natural_logarithm(), xyz }
+    }
+
+    ///`q^t = e^(t·ln q)`. For a unit quaternion this scales the rotation angle by `t`.
+    pub fn pow(self, t: S) -> Self {
+        (self.ln() * t).exp()
+    }
+
+    ///Rotation covered in `dt` at the constant angular velocity `omega` (axis times radians per second).
+    pub fn from_angular_velocity(omega: Vec3<S>, dt: S) -> Self {
+        Self::pure(omega * (dt / S::TWO)).exp()
+    }
+
+    ///Orientation after turning at the world space angular velocity `omega` for `dt`, renormalized
+    ///so repeated steps do not drift off unit length.
+    pub fn integrate(self, omega: Vec3<S>, dt: S) -> Self {
+        (Self::from_angular_velocity(omega, dt) * self).normalized()
+    }
+
+    ///Constant world space angular velocity that turns `a` into `b` in `dt` along the shorter arc,
+    ///the inverse of [`Quaternion::integrate`].
+    pub fn angular_velocity_between(a: Self, b: Self, dt: S) -> Vec3<S> {
+        let delta = (b * a.inverse()).normalized();
+        let delta = crate::ternary!(delta.w < S::ZERO => -delta ; delta);
+
+        delta.ln().xyz * (S::TWO / dt)
+    }
+
+    ///Splits the rotation into `(swing, twist)` with `self = swing * twist`, where `twist` turns about
+    ///`axis` and `swing` moves `axis` without turning about it.
+    pub fn swing_twist(self, axis: Vec3<S>) -> (Self, Self) {
+        let axis = axis.normalized();
+        let projected = axis * self.xyz.dot_product(&axis);
+        let twist = Self { w: self.w, xyz: projected };
+
+        //A half turn perpendicular to `axis` carries no twist at all.
+        if twist.magnitude() < S::from_f64(1e-6) {
+            return (self, Self::IDENTITY);
+        }
+
+        let twist = twist.normalized();
+        (self * twist.conjugate(), twist)
+    }
+
+    ///Signed angle in `[-180°, 180°]` of the twist about `axis`.
+    pub fn twist_angle(self, axis: Vec3<S>) -> Angle<S> {
+        let axis = axis.normalized();
+        let (_, twist) = self.swing_twist(axis);
+        let twist = crate::ternary!(twist.w < S::ZERO => -twist ; twist);
+
+        Angle::Radians(twist.xyz.dot_product(&axis).inverse_tangent2(twist.w) * S::TWO)
+    }
+
+    ///The same rotation, shortened to at most `max` about the same axis. Applied to a swing this
+    ///limits it to a cone of half angle `max`.
+    pub fn clamp_angle(self, max: Angle<S>) -> Self {
+        let q = crate::ternary!(self.w < S::ZERO => -self.normalized() ; self.normalized());
+        let (axis, angle) = q.to_axis_angle();
+
+        match angle.take_radians() > max.take_radians() {
+            true => Self::from_axis_angle(axis, max),
+            false => q,
+        }
+    }
+
+    ///Joint limit: keeps the swing of `axis` inside a cone of half angle `max_swing` and the twist
+    ///about `axis` within `[min_twist, max_twist]`.
+    pub fn clamp_swing_twist(self, axis: Vec3<S>, max_swing: Angle<S>, min_twist: Angle<S>, max_twist: Angle<S>) -> Self {
+        let (swing, _) = self.swing_twist(axis);
+        let twist = self
+            .twist_angle(axis)
+            .take_radians()
+            .s_clamp(min_twist.take_radians(), max_twist.take_radians());
+
+        swing.clamp_angle(max_swing) * Self::from_axis_angle(axis, Angle::Radians(twist))
+    }
+
+    ///Normalized linear interpolation along the shorter arc. Cheaper than [`Quaternion::slerp`] but
+    ///the angular velocity is not constant.
+    pub fn nlerp(a: Self, b: Self, t: S) -> Self {
+        let b = crate::ternary!(a.dot(b) < S::ZERO => -b ; b);
+        (a * (S::ONE - t) + b * t).normalized()
+    }
+
+    ///Spherical linear interpolation along the shorter arc at constant angular velocity.
+    pub fn slerp(a: Self, b: Self, t: S) -> Self {
+        let dot = a.dot(b);
+        let (b, dot) = crate::ternary!(dot < S::ZERO => (-b, -dot) ; (b, dot));
+
+        //Nearly parallel, sin(θ) would vanish.
+        if dot > S::ONE - S::from_f64(1e-6) {
+            return Self::nlerp(a, b, t);
+        }
+
+        let theta = dot.inverse_cosine();
+        let sin_theta = theta.sine();
+        a * (((S::ONE - t) * theta).sine() / sin_theta) + b * ((t * theta).sine() / sin_theta)
+    }
+
+    ///Inner control point for [`Quaternion::squad`] at keyframe `current`.
+    pub fn squad_control(previous: Self, current: Self, next: Self) -> Self {
+        let inverse = current.inverse();
+        let next = crate::ternary!(current.dot(next) < S::ZERO => -next ; next);
+        let previous = crate::ternary!(current.dot(previous) < S::ZERO => -previous ; previous);
+        let tangent = ((inverse * next).ln() + (inverse * previous).ln()) * (S::NEG_ONE / S::FOUR);
+
+        (current * tangent.exp()).normalized()
+    }
+
+    ///Spherical cubic interpolation from `a` to `b`, with control points `a_control` and
+    ///`b_control` from [`Quaternion::squad_control`], giving smooth rotation through keyframes.
+    pub fn squad(a: Self, b: Self, a_control: Self, b_control: Self, t: S) -> Self {
+        let outer = Self::slerp(a, b, t);
+        let inner = Self::slerp(a_control, b_control, t);
+
+        Self::slerp(outer, inner, S::TWO * t * (S::ONE - t))
+    }
+
     pub fn to_rotation_3x3(self) -> Matrix<3, 3, S> {
         let (q0, q1, q2, q3) = (self.w, self.xyz[0], self.xyz[1], self.xyz[2]);
 
@@ -74,14 +345,14 @@ impl<S: Scalar> Quaternion<S> {
             two * (q2 * q3 - q0 * q1)
         ]);
         let c = Vector::from_array([
-            two * q1 * q3 - q0 * q2,
+            two * (q1 * q3 - q0 * q2),
             two * (q2 * q3 + q0 * q1),
             one - two * (q1.squared() + q2.squared())
         ]);
 
         Matrix::from_array([
             a, b, c
-        ])
+        ]).conventional()
     }
 
     pub fn to_rotation_4x4(self) -> Matrix<4, 4, S> {
@@ -98,7 +369,7 @@ impl<S: Scalar> Quaternion<S> {
             S::ZERO
         ]);
         let c = Vector::from_array([
-            two * q1 * q3 - q0 * q2,
+            two * (q1 * q3 - q0 * q2),
             two * (q2 * q3 + q0 * q1),
             one - two * (q1.squared() + q2.squared()),
             S::ZERO
@@ -106,7 +377,7 @@ impl<S: Scalar> Quaternion<S> {
 
         Matrix::from_array([
             a, b, c, Vector::from_array([S::ZERO, S::ZERO, S::ZERO, S::ONE])
-        ])
+        ]).conventional()
     }
 }
 
@@ -132,6 +403,35 @@ impl<S: Scalar> Add<Self> for Quaternion<S> {
     }
 }
 
+impl<S: Scalar> Neg for Quaternion<S> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            w: -self.w,
+            xyz: -self.xyz,
+        }
+    }
+}
+
+impl<S: Scalar> Interpolation<S> for Quaternion<S> {
+    fn lerp(a: Self, b: Self, t: S) -> Self {
+        Self::slerp(a, b, t)
+    }
+
+    ///`f` receives the real quaternion `(t, 0)`, where `t` is the angular fraction of `a → v` over `a → b`,
+    ///so `|q| q.w` reads it back. Returns zero when `a` and `b` are the same orientation.
+    fn inverse_lerp<F: Fn(&Self) -> S>(a: Self, b: Self, v: Self, f: F) -> S {
+        let angle = |q: Self| a.dot(q).s_abs().s_clamp(S::ZERO, S::ONE).inverse_cosine();
+        let span = angle(b);
+        if span == S::ZERO {
+            return S::ZERO;
+        }
+
+        f(&Self { w: angle(v) / span, xyz: Vec3::ZERO })
+    }
+}
+
 impl<S: Scalar> Mul<Self> for Quaternion<S> {
     type Output = Self;
 
@@ -176,8 +476,22 @@ impl UserData for QuatF {
     }
 }
 
+impl UserData for QuatD {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        crate::quaternion_methods!(methods => f64);
+    }
+}
+
+impl UserData for QuatX {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        crate::quaternion_methods!(methods => Q16_16);
+    }
+}
+
 pub mod quatf {
+    use crate::angle::AngleF;
     use crate::euler::EulerF;
+    use crate::matrix::matrix3x3::Matrix3x3F;
     use crate::quaternion::QuatF;
     use crate::vector::vec3::Vec3F;
     use crate::{LuaAssociatedFunction, LuaObject};
@@ -189,7 +503,7 @@ pub mod quatf {
         fn create_constructor(lua: &Lua) -> rlua::Result<Function> {
             lua.create_function(|_lua, args: Self::Args| {
                 Ok(Self {
-                    w: args.0.unwrap_or(0f32),
+                    w: args.0.unwrap_or(1f32),
                     xyz: args.1.unwrap_or(Vec3F::ZERO),
                 })
             })
@@ -209,12 +523,204 @@ pub mod quatf {
                     function: _lua.create_function(|_, args: EulerF| Ok(args.to_quat()))?,
                     name: "quatf_from_euler"
                 },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, t): (Self, Self, f32)| Ok(Self::slerp(a, b, t)))?,
+                    name: "quatf_slerp"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, t): (Self, Self, f32)| Ok(Self::nlerp(a, b, t)))?,
+                    name: "quatf_nlerp"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, a_control, b_control, t): (Self, Self, Self, Self, f32)| {
+                        Ok(Self::squad(a, b, a_control, b_control, t))
+                    })?,
+                    name: "quatf_squad"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (previous, current, next): (Self, Self, Self)| {
+                        Ok(Self::squad_control(previous, current, next))
+                    })?,
+                    name: "quatf_squad_control"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (axis, angle): (Vec3F, AngleF)| Ok(Self::from_axis_angle(axis, angle)))?,
+                    name: "quatf_from_axis_angle"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (from, to): (Vec3F, Vec3F)| Ok(Self::from_rotation_arc(from, to)))?,
+                    name: "quatf_from_rotation_arc"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (forward, up): (Vec3F, Vec3F)| Ok(Self::look_rotation(forward, up)))?,
+                    name: "quatf_look_rotation"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, m: Matrix3x3F| Ok(Self::from_rotation_matrix(m)))?,
+                    name: "quatf_from_rotation_matrix"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (omega, dt): (Vec3F, f32)| Ok(Self::from_angular_velocity(omega, dt)))?,
+                    name: "quatf_from_angular_velocity"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, dt): (Self, Self, f32)| Ok(Self::angular_velocity_between(a, b, dt)))?,
+                    name: "quatf_angular_velocity_between"
+                },
             ])
         }
     }
 }
 
-pub mod quatd {}
+pub mod quatd {
+    use crate::angle::AngleD;
+    use crate::euler::EulerD;
+    use crate::matrix::matrix3x3::Matrix3x3D;
+    use crate::quaternion::QuatD;
+    use crate::vector::vec3::Vec3D;
+    use crate::{LuaAssociatedFunction, LuaObject};
+    use rlua::{Function, Lua};
+    impl LuaObject for QuatD {
+        type Args = (Option<f64>, Option<Vec3D>);
+        const CONSTRUCTOR_NAME: &'static str = "quatd";
+
+        fn create_constructor(lua: &Lua) -> rlua::Result<Function> {
+            lua.create_function(|_lua, args: Self::Args| {
+                Ok(Self {
+                    w: args.0.unwrap_or(1f64),
+                    xyz: args.1.unwrap_or(Vec3D::ZERO),
+                })
+            })
+        }
+
+        fn associated_functions(_lua: &Lua) -> rlua::Result<Vec<LuaAssociatedFunction>> {
+            Ok(vec![
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, _args: ()| Ok(Self::IDENTITY))?,
+                    name: "quatd_identity"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, args: Vec3D| Ok(Self::pure(args)))?,
+                    name: "quatd_pure"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, args: EulerD| Ok(args.to_quat()))?,
+                    name: "quatd_from_euler"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, t): (Self, Self, f64)| Ok(Self::slerp(a, b, t)))?,
+                    name: "quatd_slerp"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, t): (Self, Self, f64)| Ok(Self::nlerp(a, b, t)))?,
+                    name: "quatd_nlerp"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, a_control, b_control, t): (Self, Self, Self, Self, f64)| {
+                        Ok(Self::squad(a, b, a_control, b_control, t))
+                    })?,
+                    name: "quatd_squad"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (previous, current, next): (Self, Self, Self)| {
+                        Ok(Self::squad_control(previous, current, next))
+                    })?,
+                    name: "quatd_squad_control"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (axis, angle): (Vec3D, AngleD)| Ok(Self::from_axis_angle(axis, angle)))?,
+                    name: "quatd_from_axis_angle"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (from, to): (Vec3D, Vec3D)| Ok(Self::from_rotation_arc(from, to)))?,
+                    name: "quatd_from_rotation_arc"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (forward, up): (Vec3D, Vec3D)| Ok(Self::look_rotation(forward, up)))?,
+                    name: "quatd_look_rotation"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, m: Matrix3x3D| Ok(Self::from_rotation_matrix(m)))?,
+                    name: "quatd_from_rotation_matrix"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (omega, dt): (Vec3D, f64)| Ok(Self::from_angular_velocity(omega, dt)))?,
+                    name: "quatd_from_angular_velocity"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, dt): (Self, Self, f64)| Ok(Self::angular_velocity_between(a, b, dt)))?,
+                    name: "quatd_angular_velocity_between"
+                },
+            ])
+        }
+    }
+}
+
+pub mod quatx {
+    use crate::angle::AngleX;
+    use crate::euler::EulerX;
+    use crate::fixed::Q16_16;
+    use crate::matrix::matrix3x3::Matrix3x3X;
+    use crate::quaternion::QuatX;
+    use crate::scalar::Scalar;
+    use crate::vector::vec3::Vec3X;
+    use crate::{LuaAssociatedFunction, LuaObject};
+    use rlua::{Function, Lua};
+    impl LuaObject for QuatX {
+        type Args = (Option<Q16_16>, Option<Vec3X>);
+        const CONSTRUCTOR_NAME: &'static str = "quatx";
+
+        fn create_constructor(lua: &Lua) -> rlua::Result<Function> {
+            lua.create_function(|_lua, args: Self::Args| {
+                Ok(Self {
+                    w: args.0.unwrap_or(Q16_16::ONE),
+                    xyz: args.1.unwrap_or(Vec3X::ZERO),
+                })
+            })
+        }
+
+        fn associated_functions(_lua: &Lua) -> rlua::Result<Vec<LuaAssociatedFunction>> {
+            Ok(vec![
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, _args: ()| Ok(Self::IDENTITY))?,
+                    name: "quatx_identity"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, args: EulerX| Ok(args.to_quat()))?,
+                    name: "quatx_from_euler"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, t): (Self, Self, Q16_16)| Ok(Self::slerp(a, b, t)))?,
+                    name: "quatx_slerp"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (a, b, t): (Self, Self, Q16_16)| Ok(Self::nlerp(a, b, t)))?,
+                    name: "quatx_nlerp"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (axis, angle): (Vec3X, AngleX)| Ok(Self::from_axis_angle(axis, angle)))?,
+                    name: "quatx_from_axis_angle"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (from, to): (Vec3X, Vec3X)| Ok(Self::from_rotation_arc(from, to)))?,
+                    name: "quatx_from_rotation_arc"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (forward, up): (Vec3X, Vec3X)| Ok(Self::look_rotation(forward, up)))?,
+                    name: "quatx_look_rotation"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, m: Matrix3x3X| Ok(Self::from_rotation_matrix(m)))?,
+                    name: "quatx_from_rotation_matrix"
+                },
+                LuaAssociatedFunction {
+                    function: _lua.create_function(|_, (omega, dt): (Vec3X, Q16_16)| Ok(Self::from_angular_velocity(omega, dt)))?,
+                    name: "quatx_from_angular_velocity"
+                },
+            ])
+        }
+    }
+}
 
 #[macro_export]
 macro_rules! quaternion_methods {
@@ -224,6 +730,35 @@ macro_rules! quaternion_methods {
         $methods.add_method("magnitude", |_lua, this, ()| Ok(this.magnitude()));
         $methods.add_method("inverse", |_lua, this, ()| Ok(this.inverse()));
         $methods.add_method("conjugate", |_lua, this, ()| Ok(this.conjugate()));
+        $methods.add_method("to_axis_angle", |_lua, this, ()| Ok(this.to_axis_angle()));
+        $methods.add_method("to_euler", |_lua, this, order: Option<RotationOrder>| {
+            Ok(this.to_euler(order.unwrap_or_default()))
+        });
+        $methods.add_method("normalized", |_lua, this, ()| Ok(this.normalized()));
+        $methods.add_method_mut("normalize", |_lua, this, ()| {
+            this.normalize();
+            Ok(())
+        });
+        $methods.add_method("is_normalized", |_lua, this, ()| Ok(this.is_normalized()));
+        $methods.add_method("dot", |_lua, this, b: Self| Ok(this.dot(b)));
+        $methods.add_method("angle_to", |_lua, this, b: Self| Ok(this.angle_to(b)));
+        $methods.add_method("rotate", |_lua, this, v: Vec3<$f>| Ok(this.rotate(v)));
+        $methods.add_method("forward", |_lua, this, ()| Ok(this.forward()));
+        $methods.add_method("right", |_lua, this, ()| Ok(this.right()));
+        $methods.add_method("up", |_lua, this, ()| Ok(this.up()));
+        $methods.add_method("swing_twist", |_lua, this, axis: Vec3<$f>| Ok(this.swing_twist(axis)));
+        $methods.add_method("twist_angle", |_lua, this, axis: Vec3<$f>| Ok(this.twist_angle(axis)));
+        $methods.add_method("clamp_angle", |_lua, this, max: Angle<$f>| Ok(this.clamp_angle(max)));
+        $methods.add_method(
+            "clamp_swing_twist",
+            |_lua, this, (axis, max_swing, min_twist, max_twist): (Vec3<$f>, Angle<$f>, Angle<$f>, Angle<$f>)| {
+                Ok(this.clamp_swing_twist(axis, max_swing, min_twist, max_twist))
+            },
+        );
+        $methods.add_method("exp", |_lua, this, ()| Ok(this.exp()));
+        $methods.add_method("ln", |_lua, this, ()| Ok(this.ln()));
+        $methods.add_method("pow", |_lua, this, t: $f| Ok(this.pow(t)));
+        $methods.add_method("integrate", |_lua, this, (omega, dt): (Vec3<$f>, $f)| Ok(this.integrate(omega, dt)));
 
         $methods.add_method(format!("to_mat3x3{}", <$f>::NAME), |_lua, this, ()| Ok(this.to_rotation_3x3()));
         $methods.add_method(format!("to_mat4x4{}", <$f>::NAME), |_lua, this, ()| Ok(this.to_rotation_4x4()));
@@ -238,31 +773,33 @@ macro_rules! quaternion_methods {
             }
 
             if let Ok(b) = b.borrow::<Number>() {
-                return Ok(*this + *b as $f);
+                return Ok(*this + <$f as $crate::scalar::Scalar>::from_f64(*b));
             }
             if let Ok(b) = b.borrow::<Integer>() {
-                return Ok(*this + *b as $f);
+                return Ok(*this + <$f as $crate::scalar::Scalar>::from_f64(*b as f64));
             }
 
             Ok(*this)
         });
 
-        $methods.add_meta_method(MetaMethod::Mul, |_lua, this, b: AnyUserData| {
-            if let Ok(b) = b.borrow::<Self>() {
-                return Ok(*this * *b);
-            }
-            if let Ok(b) = b.borrow::<Vec3<$f>>() {
-                return Ok(Quaternion::pure(*this * *b));
+        $methods.add_meta_method(MetaMethod::Eq, |_lua, this, b: Self| Ok(*this == b));
+
+        $methods.add_meta_function(MetaMethod::Mul, |lua, (a, b): (rlua::Value, rlua::Value)| {
+            match (a, b) {
+                (rlua::Value::UserData(a), rlua::Value::UserData(b)) => {
+                    let a = *a.borrow::<Self>()?;
+                    if let Ok(b) = b.borrow::<Self>() {
+                        return rlua::IntoLua::into_lua(a * *b, lua);
+                    }
+                    rlua::IntoLua::into_lua(a * *b.borrow::<Vec3<$f>>()?, lua)
+                }
+                (rlua::Value::UserData(q), s) | (s, rlua::Value::UserData(q)) => {
+                    rlua::IntoLua::into_lua(*q.borrow::<Self>()? * <$f as rlua::FromLua>::from_lua(s, lua)?, lua)
+                }
+                (a, b) => Err(rlua::Error::RuntimeError(format!(
+                    "cannot multiply {} by {}", a.type_name(), b.type_name()
+                ))),
             }
-
-            if let Ok(b) = b.borrow::<Number>() {
-                return Ok(*this * *b as $f);
-            }
-            if let Ok(b) = b.borrow::<Integer>() {
-                return Ok(*this * *b as $f);
-            }
-
-            Ok(*this)
         });
     };
 }
\ No newline at end of file
@@ -0,0 +1,23 @@
+use crate::scalar::Scalar;
+use crate::vector::vec3::Vec3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray<S: Scalar> {
+    pub origin: Vec3<S>,
+    ///Unit length when built by this crate.
+    pub direction: Vec3<S>,
+}
+
+impl<S: Scalar> Ray<S> {
+    pub fn new(origin: Vec3<S>, direction: Vec3<S>) -> Self {
+        Self {
+            origin,
+            direction: direction.normalized(),
+        }
+    }
+
+    ///The point `t` units along the ray.
+    pub fn at(&self, t: S) -> Vec3<S> {
+        self.origin + self.direction * t
+    }
+}
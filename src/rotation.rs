@@ -0,0 +1,224 @@
+use crate::angle::{Angle, AngleD, AngleF};
+use crate::euler::{Euler, EulerD, EulerF};
+use crate::matrix::matrix3x3::{Matrix3x3, Matrix3x3D, Matrix3x3F, RotationOrder};
+use crate::quaternion::{QuatD, QuatF, Quaternion};
+use crate::scalar::Scalar;
+use crate::vector::vec3::{Vec3, Vec3D, Vec3F};
+use crate::{LuaAssociatedFunction, LuaObject};
+use mlua::FromLua;
+use rlua::{Function, Lua, MetaMethod, UserData, UserDataMethods};
+
+pub type AxisAngleF = AxisAngle<f32>;
+pub type AxisAngleD = AxisAngle<f64>;
+
+///Common ground for every rotation representation. Conversions go through [`Quaternion`], so any
+///representation turns into any other with [`Rotation::convert`].
+pub trait Rotation<S: Scalar>: Copy {
+    fn to_quaternion(self) -> Quaternion<S>;
+    fn from_quaternion(q: Quaternion<S>) -> Self;
+
+    fn convert<R: Rotation<S>>(self) -> R {
+        R::from_quaternion(self.to_quaternion())
+    }
+
+    ///Rotation applying `self` first, then `next`, as [`Matrix::then`](crate::matrix::Matrix::then) does.
+    fn then<R: Rotation<S>>(self, next: R) -> Self {
+        Self::from_quaternion(next.to_quaternion() * self.to_quaternion())
+    }
+
+    ///Rotation undoing `self`.
+    fn invert(self) -> Self {
+        Self::from_quaternion(self.to_quaternion().normalized().conjugate())
+    }
+
+    fn rotate(self, v: Vec3<S>) -> Vec3<S> {
+        self.to_quaternion().normalized() * v
+    }
+}
+
+///Rotation by `angle` about `axis`.
+#[derive(Debug, Clone, Copy, PartialEq, FromLua)]
+pub struct AxisAngle<S: Scalar> {
+    pub axis: Vec3<S>,
+    pub angle: Angle<S>,
+}
+
+impl<S: Scalar> AxisAngle<S> {
+    pub fn new(axis: Vec3<S>, angle: Angle<S>) -> Self {
+        Self { axis: axis.normalized(), angle }
+    }
+}
+
+impl<S: Scalar> Rotation<S> for AxisAngle<S> {
+    fn to_quaternion(self) -> Quaternion<S> {
+        Quaternion::from_axis_angle(self.axis, self.angle)
+    }
+
+    fn from_quaternion(q: Quaternion<S>) -> Self {
+        let (axis, angle) = q.to_axis_angle();
+        Self { axis, angle }
+    }
+}
+
+impl<S: Scalar> Rotation<S> for Quaternion<S> {
+    fn to_quaternion(self) -> Quaternion<S> {
+        self
+    }
+
+    fn from_quaternion(q: Quaternion<S>) -> Self {
+        q
+    }
+}
+
+impl<S: Scalar> Rotation<S> for Matrix3x3<S> {
+    fn to_quaternion(self) -> Quaternion<S> {
+        Quaternion::from_rotation_matrix(self)
+    }
+
+    fn from_quaternion(q: Quaternion<S>) -> Self {
+        q.normalized().to_rotation_3x3()
+    }
+}
+
+///Composes in the stored order, comes back in the default [`RotationOrder`].
+impl<S: Scalar> Rotation<S> for Euler<S> {
+    fn to_quaternion(self) -> Quaternion<S> {
+        self.to_quat()
+    }
+
+    fn from_quaternion(q: Quaternion<S>) -> Self {
+        q.to_euler(RotationOrder::default())
+    }
+}
+
+///Whichever rotation a script passed in, so Lua functions can take any representation and hand
+///back the same one.
+#[derive(Debug, Clone, Copy)]
+pub enum AnyRotation<S: Scalar> {
+    Quaternion(Quaternion<S>),
+    Euler(Euler<S>),
+    Matrix(Matrix3x3<S>),
+    AxisAngle(AxisAngle<S>),
+}
+
+impl<S: Scalar> AnyRotation<S> {
+    ///The same kind of rotation as `self`, holding `q`.
+    fn with_quaternion(self, q: Quaternion<S>) -> Self {
+        match self {
+            AnyRotation::Quaternion(_) => AnyRotation::Quaternion(q),
+            AnyRotation::Euler(_) => AnyRotation::Euler(Euler::from_quaternion(q)),
+            AnyRotation::Matrix(_) => AnyRotation::Matrix(Matrix3x3::from_quaternion(q)),
+            AnyRotation::AxisAngle(_) => AnyRotation::AxisAngle(AxisAngle::from_quaternion(q)),
+        }
+    }
+}
+
+impl<S: Scalar> Rotation<S> for AnyRotation<S> {
+    fn to_quaternion(self) -> Quaternion<S> {
+        match self {
+            AnyRotation::Quaternion(q) => q,
+            AnyRotation::Euler(e) => e.to_quaternion(),
+            AnyRotation::Matrix(m) => m.to_quaternion(),
+            AnyRotation::AxisAngle(a) => a.to_quaternion(),
+        }
+    }
+
+    fn from_quaternion(q: Quaternion<S>) -> Self {
+        AnyRotation::Quaternion(q)
+    }
+
+    fn then<R: Rotation<S>>(self, next: R) -> Self {
+        self.with_quaternion(next.to_quaternion() * self.to_quaternion())
+    }
+
+    fn invert(self) -> Self {
+        self.with_quaternion(self.to_quaternion().normalized().conjugate())
+    }
+}
+
+macro_rules! lua_rotation {
+    ($f:ty => $quat:ty, $euler:ty, $mat:ty, $axis_angle:ty, $vec3:ty, $angle:ty, $suffix:literal) => {
+        impl<'lua> FromLua<'lua> for AnyRotation<$f> {
+            fn from_lua(value: rlua::Value<'lua>, _lua: &'lua Lua) -> rlua::Result<Self> {
+                if let rlua::Value::UserData(ud) = &value {
+                    if let Ok(q) = ud.borrow::<$quat>() {
+                        return Ok(AnyRotation::Quaternion(*q));
+                    }
+                    if let Ok(e) = ud.borrow::<$euler>() {
+                        return Ok(AnyRotation::Euler(*e));
+                    }
+                    if let Ok(m) = ud.borrow::<$mat>() {
+                        return Ok(AnyRotation::Matrix(*m));
+                    }
+                    if let Ok(a) = ud.borrow::<$axis_angle>() {
+                        return Ok(AnyRotation::AxisAngle(*a));
+                    }
+                }
+                Err(rlua::Error::RuntimeError(format!(
+                    concat!("expected quat", $suffix, ", euler", $suffix, ", mat3x3", $suffix, " or axis_angle", $suffix, ", found {}"),
+                    value.type_name()
+                )))
+            }
+        }
+
+        impl<'lua> rlua::IntoLua<'lua> for AnyRotation<$f> {
+            fn into_lua(self, lua: &'lua Lua) -> rlua::Result<rlua::Value<'lua>> {
+                match self {
+                    AnyRotation::Quaternion(q) => q.into_lua(lua),
+                    AnyRotation::Euler(e) => e.into_lua(lua),
+                    AnyRotation::Matrix(m) => m.into_lua(lua),
+                    AnyRotation::AxisAngle(a) => a.into_lua(lua),
+                }
+            }
+        }
+
+        impl AnyRotation<$f> {
+            #[doc = concat!("Registers the `rot", $suffix, "_*` functions, which take any `", stringify!($f), "` rotation.")]
+            pub fn load_lua(lua: &Lua) -> rlua::Result<()> {
+                let globals = lua.globals();
+                globals.set(concat!("rot", $suffix, "_to_quat"), lua.create_function(|_, r: Self| Ok(r.to_quaternion()))?)?;
+                globals.set(concat!("rot", $suffix, "_to_mat3x3"), lua.create_function(|_, r: Self| Ok(r.convert::<$mat>()))?)?;
+                globals.set(
+                    concat!("rot", $suffix, "_to_axis_angle"),
+                    lua.create_function(|_, r: Self| Ok(r.convert::<$axis_angle>()))?,
+                )?;
+                globals.set(
+                    concat!("rot", $suffix, "_to_euler"),
+                    lua.create_function(|_, (r, order): (Self, Option<RotationOrder>)| {
+                        Ok(r.to_quaternion().to_euler(order.unwrap_or_default()))
+                    })?,
+                )?;
+                globals.set(concat!("rot", $suffix, "_then"), lua.create_function(|_, (a, b): (Self, Self)| Ok(a.then(b)))?)?;
+                globals.set(concat!("rot", $suffix, "_inverse"), lua.create_function(|_, r: Self| Ok(r.invert()))?)?;
+                globals.set(concat!("rot", $suffix, "_rotate"), lua.create_function(|_, (r, v): (Self, $vec3)| Ok(r.rotate(v)))?)?;
+                Ok(())
+            }
+        }
+
+        impl UserData for $axis_angle {
+            fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+                methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{} about {}", this.angle, this.axis)));
+                methods.add_meta_method(MetaMethod::Eq, |_, this, b: Self| Ok(*this == b));
+                methods.add_method("axis", |_, this, ()| Ok(this.axis));
+                methods.add_method("angle", |_, this, ()| Ok(this.angle));
+                methods.add_method("to_quat", |_, this, ()| Ok(this.to_quaternion()));
+            }
+        }
+
+        impl LuaObject for $axis_angle {
+            type Args = ($vec3, $angle);
+            const CONSTRUCTOR_NAME: &'static str = concat!("axis_angle", $suffix);
+
+            fn create_constructor(lua: &Lua) -> rlua::Result<Function> {
+                lua.create_function(|_, (axis, angle): Self::Args| Ok(Self::new(axis, angle)))
+            }
+
+            fn associated_functions(_lua: &Lua) -> rlua::Result<Vec<LuaAssociatedFunction>> {
+                Ok(vec![])
+            }
+        }
+    };
+}
+
+lua_rotation!(f32 => QuatF, EulerF, Matrix3x3F, AxisAngleF, Vec3F, AngleF, "f");
+lua_rotation!(f64 => QuatD, EulerD, Matrix3x3D, AxisAngleD, Vec3D, AngleD, "d");
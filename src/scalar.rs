@@ -3,6 +3,7 @@ use std::iter::Sum;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use crate::interpolation::Interpolation;
 use crate::percentage::Percentage;
+use rlua::Lua;
 
 crate::scalar!(f32 => "f");
 crate::scalar!(f64 => "d");
@@ -37,6 +38,9 @@ pub trait Scalar:
     const FIVE: Self;
 
     const PI: Self;
+    ///Difference between `1` and the next representable value.
+    const EPSILON: Self;
+    const INFINITY: Self;
     const NAME: &'static str;
 
     fn sine(self) -> Self;
@@ -45,6 +49,9 @@ pub trait Scalar:
 
     fn inverse_sine(self) -> Self;
     fn inverse_cosine(self) -> Self;
+    fn inverse_tangent(self) -> Self;
+    ///Four quadrant arctangent of `self / x`.
+    fn inverse_tangent2(self, x: Self) -> Self;
 
     fn power_i(self, i: i32) -> Self;
     fn power_f(self, f: Self) -> Self;
@@ -55,6 +62,47 @@ pub trait Scalar:
         self.power_f(Self::ONE / Self::TWO)
     }
     fn square_root2(self) -> Self;
+    fn exponential(self) -> Self;
+    fn natural_logarithm(self) -> Self;
+    ///Logarithm of `self` in `base`.
+    fn log(self, base: Self) -> Self;
+
+    ///Same as `inverse_tangent2`.
+    fn atan2(self, x: Self) -> Self {
+        self.inverse_tangent2(x)
+    }
+    ///Same as `exponential`.
+    fn exp(self) -> Self {
+        self.exponential()
+    }
+    ///Same as `natural_logarithm`.
+    fn ln(self) -> Self {
+        self.natural_logarithm()
+    }
+
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    ///Rounds half-way cases away from zero.
+    fn round(self) -> Self;
+    ///`self` minus its integer part, keeping the sign of `self`.
+    fn fract(self) -> Self;
+    fn signum(self) -> Self;
+    ///Magnitude of `self` with the sign of `sign`.
+    fn copysign(self, sign: Self) -> Self;
+    ///`self * a + b` with a single rounding.
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    ///Length of the hypotenuse `sqrt(self² + other²)` without intermediate overflow.
+    fn hypot(self, other: Self) -> Self;
+    ///`sqrt(Σ v²)`, which narrow formats compute without saturating the squares.
+    fn euclidean_norm(values: &[Self]) -> Self {
+        values.iter().map(|s| s.squared()).sum::<Self>().square_root2()
+    }
+    ///`Σ a·b`, which narrow formats accumulate without saturating the partial sums.
+    fn sum_of_products(a: &[Self], b: &[Self]) -> Self {
+        a.iter().zip(b).map(|(a, b)| *a * *b).sum::<Self>()
+    }
+    fn is_nan(self) -> bool;
+    fn is_finite(self) -> bool;
 
     fn from_f32(f: f32) -> Self;
     fn from_f64(f: f64) -> Self;
@@ -81,6 +129,28 @@ pub trait Scalar:
     }
 }
 
+///Registers the `mathx` table, the `Scalar` helpers Lua's `math` lacks, on Lua numbers.
+pub fn load_mathx(lua: &Lua) -> rlua::Result<()> {
+    let mathx = lua.create_table()?;
+    mathx.set("epsilon", f64::EPSILON)?;
+    mathx.set("infinity", f64::INFINITY)?;
+    mathx.set("atan2", lua.create_function(|_, (y, x): (f64, f64)| Ok(Scalar::atan2(y, x)))?)?;
+    mathx.set("exp", lua.create_function(|_, x: f64| Ok(Scalar::exp(x)))?)?;
+    mathx.set("ln", lua.create_function(|_, x: f64| Ok(Scalar::ln(x)))?)?;
+    mathx.set("log", lua.create_function(|_, (x, base): (f64, f64)| Ok(Scalar::log(x, base)))?)?;
+    mathx.set("min", lua.create_function(|_, (a, b): (f64, f64)| Ok(Scalar::min(a, b)))?)?;
+    mathx.set("max", lua.create_function(|_, (a, b): (f64, f64)| Ok(Scalar::max(a, b)))?)?;
+    mathx.set("round", lua.create_function(|_, x: f64| Ok(Scalar::round(x)))?)?;
+    mathx.set("fract", lua.create_function(|_, x: f64| Ok(Scalar::fract(x)))?)?;
+    mathx.set("signum", lua.create_function(|_, x: f64| Ok(Scalar::signum(x)))?)?;
+    mathx.set("copysign", lua.create_function(|_, (x, sign): (f64, f64)| Ok(Scalar::copysign(x, sign)))?)?;
+    mathx.set("mul_add", lua.create_function(|_, (x, a, b): (f64, f64, f64)| Ok(Scalar::mul_add(x, a, b)))?)?;
+    mathx.set("hypot", lua.create_function(|_, (a, b): (f64, f64)| Ok(Scalar::hypot(a, b)))?)?;
+    mathx.set("is_nan", lua.create_function(|_, x: f64| Ok(Scalar::is_nan(x)))?)?;
+    mathx.set("is_finite", lua.create_function(|_, x: f64| Ok(Scalar::is_finite(x)))?)?;
+    lua.globals().set("mathx", mathx)
+}
+
 #[macro_export]
 macro_rules! scalar {
     ($t:ty => $name:literal) => {
@@ -94,6 +164,8 @@ macro_rules! scalar {
             const FIVE: Self = 5f64 as $t;
 
             const PI: Self = std::f64::consts::PI as $t;
+            const EPSILON: Self = <$t>::EPSILON;
+            const INFINITY: Self = <$t>::INFINITY;
             const NAME: &'static str = $name;
 
             fn sine(self) -> Self {
@@ -116,6 +188,14 @@ macro_rules! scalar {
                 self.acos()
             }
 
+            fn inverse_tangent(self) -> Self {
+                self.atan()
+            }
+
+            fn inverse_tangent2(self, x: Self) -> Self {
+                self.atan2(x)
+            }
+
             fn power_i(self, i: i32) -> Self {
                 self.powi(i)
             }
@@ -128,6 +208,58 @@ macro_rules! scalar {
                 self.sqrt()
             }
 
+            fn exponential(self) -> Self {
+                self.exp()
+            }
+
+            fn natural_logarithm(self) -> Self {
+                self.ln()
+            }
+
+            fn log(self, base: Self) -> Self {
+                self.log(base)
+            }
+
+            fn min(self, other: Self) -> Self {
+                self.min(other)
+            }
+
+            fn max(self, other: Self) -> Self {
+                self.max(other)
+            }
+
+            fn round(self) -> Self {
+                self.round()
+            }
+
+            fn fract(self) -> Self {
+                self.fract()
+            }
+
+            fn signum(self) -> Self {
+                self.signum()
+            }
+
+            fn copysign(self, sign: Self) -> Self {
+                self.copysign(sign)
+            }
+
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                self.mul_add(a, b)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                self.hypot(other)
+            }
+
+            fn is_nan(self) -> bool {
+                self.is_nan()
+            }
+
+            fn is_finite(self) -> bool {
+                self.is_finite()
+            }
+
             fn from_f32(f: f32) -> Self {
                 f as $t
             }
@@ -0,0 +1,232 @@
+use crate::lua::check_bounds;
+use crate::matrix::dmatrix::DimensionMismatch;
+use crate::scalar::Scalar;
+use crate::vector::vec2::Vec2D;
+use crate::vector::vec3::Vec3D;
+use crate::vector::vec4::Vec4D;
+use crate::vector::Vector;
+use crate::{LuaAssociatedFunction, LuaObject};
+use mlua::FromLua;
+use rlua::{Function, Lua, UserData, UserDataMethods};
+use std::fmt::Display;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+
+pub type DVectorD = DVector<f64>;
+
+///Heap backed vector whose length is only known at runtime, for scripts that cannot name a `Vector<L, S>`.
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct DVector<S: Scalar>(Vec<S>);
+
+impl<S: Scalar> DVector<S> {
+    pub fn zeros(len: usize) -> Self {
+        Self(vec![S::ZERO; len])
+    }
+
+    pub fn from_vec(inner: Vec<S>) -> Self {
+        Self(inner)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[S] {
+        &self.0
+    }
+
+    pub fn magnitude(&self) -> S {
+        S::euclidean_norm(&self.0)
+    }
+
+    pub fn dot_product(&self, other: &Self) -> Result<S, DimensionMismatch> {
+        self.check_len(other)?;
+        Ok(S::sum_of_products(&self.0, &other.0))
+    }
+
+    pub fn normalized(&self) -> Self {
+        self.clone() / self.magnitude()
+    }
+
+    pub(crate) fn check_len(&self, other: &Self) -> Result<(), DimensionMismatch> {
+        match self.len() == other.len() {
+            true => Ok(()),
+            false => Err(DimensionMismatch {
+                expected: (self.len(), 1),
+                found: (other.len(), 1),
+            }),
+        }
+    }
+
+    pub fn try_add(&self, rhs: &Self) -> Result<Self, DimensionMismatch> {
+        self.check_len(rhs)?;
+        Ok(Self(self.0.iter().zip(&rhs.0).map(|(a, b)| *a + *b).collect()))
+    }
+
+    pub fn try_sub(&self, rhs: &Self) -> Result<Self, DimensionMismatch> {
+        self.check_len(rhs)?;
+        Ok(Self(self.0.iter().zip(&rhs.0).map(|(a, b)| *a - *b).collect()))
+    }
+}
+
+impl<S: Scalar> Index<usize> for DVector<S> {
+    type Output = S;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<S: Scalar> IndexMut<usize> for DVector<S> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<S: Scalar> Display for DVector<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.0.iter().map(|e| e.to_string()).collect::<Vec<String>>().join(", ");
+        write!(f, "<{inner}>")
+    }
+}
+
+///Panics when the lengths differ, see [`DVector::try_add`].
+impl<S: Scalar> Add<Self> for DVector<S> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.try_add(&rhs).unwrap()
+    }
+}
+
+///Panics when the lengths differ, see [`DVector::try_sub`].
+impl<S: Scalar> Sub<Self> for DVector<S> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.try_sub(&rhs).unwrap()
+    }
+}
+
+impl<S: Scalar> Mul<S> for DVector<S> {
+    type Output = Self;
+
+    fn mul(self, rhs: S) -> Self::Output {
+        Self(self.0.into_iter().map(|s| s * rhs).collect())
+    }
+}
+
+impl<S: Scalar> Div<S> for DVector<S> {
+    type Output = Self;
+
+    fn div(self, rhs: S) -> Self::Output {
+        Self(self.0.into_iter().map(|s| s / rhs).collect())
+    }
+}
+
+impl<S: Scalar> Neg for DVector<S> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.into_iter().map(|s| -s).collect())
+    }
+}
+
+impl<const L: usize, S: Scalar> From<Vector<L, S>> for DVector<S> {
+    fn from(v: Vector<L, S>) -> Self {
+        Self(v.0.to_vec())
+    }
+}
+
+impl<const L: usize, S: Scalar> TryFrom<DVector<S>> for Vector<L, S> {
+    type Error = DimensionMismatch;
+
+    fn try_from(v: DVector<S>) -> Result<Self, Self::Error> {
+        let found = v.len();
+        v.0.try_into().map(Vector::from_array).map_err(|_| DimensionMismatch {
+            expected: (L, 1),
+            found: (found, 1),
+        })
+    }
+}
+
+impl UserData for DVectorD {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(rlua::MetaMethod::ToString, |_, this, _: ()| Ok(this.to_string()));
+        methods.add_meta_method(rlua::MetaMethod::Len, |_, this, _: ()| Ok(this.len()));
+        methods.add_meta_method(rlua::MetaMethod::Index, |_, this, index: usize| Ok(this.0.get(index).copied()));
+        methods.add_method_mut("set", |_, this, (index, v): (usize, f64)| {
+            let index = check_bounds(index, this.len())?;
+            this[index] = v;
+            Ok(())
+        });
+
+        methods.add_meta_method(rlua::MetaMethod::Eq, |_, this, b: Self| Ok(*this == b));
+        methods.add_meta_method(rlua::MetaMethod::Unm, |_, this, _: ()| Ok(-this.clone()));
+        methods.add_meta_method(rlua::MetaMethod::Add, |_, this, b: Self| this.try_add(&b).map_err(lua_error));
+        methods.add_meta_method(rlua::MetaMethod::Sub, |_, this, b: Self| this.try_sub(&b).map_err(lua_error));
+        methods.add_meta_function(rlua::MetaMethod::Mul, |lua, (a, b): (rlua::Value, rlua::Value)| {
+            match (a, b) {
+                (rlua::Value::UserData(v), s) | (s, rlua::Value::UserData(v)) => {
+                    Ok(v.borrow::<Self>()?.clone() * f64::from_lua(s, lua)?)
+                }
+                (a, b) => Err(rlua::Error::RuntimeError(format!(
+                    "cannot multiply {} by {}", a.type_name(), b.type_name()
+                ))),
+            }
+        });
+        methods.add_meta_function(rlua::MetaMethod::Div, |_, (this, s): (Self, f64)| Ok(this / s));
+
+        methods.add_method("len", |_, this, _: ()| Ok(this.len()));
+        methods.add_method("magnitude", |_, this, _: ()| Ok(this.magnitude()));
+        methods.add_method("dot", |_, this, b: Self| this.dot_product(&b).map_err(lua_error));
+        methods.add_method("norm", |_, this, _: ()| Ok(this.normalized()));
+        methods.add_method("to_table", |_, this, _: ()| Ok(this.0.clone()));
+        methods.add_method("to_fixed", |lua, this, _: ()| {
+            match this.len() {
+                2 => rlua::IntoLua::into_lua(Vec2D::try_from(this.clone()).map_err(lua_error)?, lua),
+                3 => rlua::IntoLua::into_lua(Vec3D::try_from(this.clone()).map_err(lua_error)?, lua),
+                4 => rlua::IntoLua::into_lua(Vec4D::try_from(this.clone()).map_err(lua_error)?, lua),
+                n => Err(rlua::Error::RuntimeError(format!("no fixed size vector of length {n}"))),
+            }
+        });
+    }
+}
+
+pub(crate) fn lua_error(e: DimensionMismatch) -> rlua::Error {
+    rlua::Error::RuntimeError(e.to_string())
+}
+
+impl LuaObject for DVectorD {
+    ///Either a length, giving zeros, or a table of components.
+    type Args = rlua::Value<'static>;
+    const CONSTRUCTOR_NAME: &'static str = "vecd";
+
+    fn create_constructor(lua: &Lua) -> rlua::Result<Function> {
+        lua.create_function(|lua, args: rlua::Value| match args {
+            rlua::Value::Table(_) => Ok(Self::from_vec(Vec::<f64>::from_lua(args, lua)?)),
+            len => Ok(Self::zeros(usize::from_lua(len, lua)?)),
+        })
+    }
+
+    fn associated_functions(lua: &Lua) -> rlua::Result<Vec<LuaAssociatedFunction>> {
+        Ok(vec![LuaAssociatedFunction {
+            name: "vecd_from",
+            function: lua.create_function(|_, v: rlua::AnyUserData| {
+                if let Ok(v) = v.borrow::<Vec2D>() {
+                    return Ok(Self::from(*v));
+                }
+                if let Ok(v) = v.borrow::<Vec3D>() {
+                    return Ok(Self::from(*v));
+                }
+                if let Ok(v) = v.borrow::<Vec4D>() {
+                    return Ok(Self::from(*v));
+                }
+                Err(rlua::Error::RuntimeError("vecd_from expects a vec2d, vec3d or vec4d".to_string()))
+            })?,
+        }])
+    }
+}
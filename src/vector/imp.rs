@@ -15,7 +15,7 @@ impl<const L: usize, S: Scalar> Vector<L, S> {
     }
 
     pub fn magnitude(&self) -> S {
-        self.length().square_root2()
+        S::euclidean_norm(&self.0)
     }
 
     pub fn length(&self) -> S {
@@ -23,10 +23,7 @@ impl<const L: usize, S: Scalar> Vector<L, S> {
     }
 
     pub fn dot_product(&self, other: &Self) -> S {
-        (0..L)
-            .into_iter()
-            .map(|i| self.0[i] * other.0[i])
-            .sum::<S>()
+        S::sum_of_products(&self.0, &other.0)
     }
 
     pub fn normalized(&self) -> Self {
@@ -2,6 +2,7 @@ use crate::scalar::Scalar;
 use mlua::FromLua;
 
 pub mod imp;
+pub mod dvector;
 pub mod vec2;
 pub mod vec3;
 pub mod vec4;
@@ -31,10 +32,10 @@ macro_rules! lua_vector_methods {
                 return Ok(*this + *index);
             }
             if let Ok(index) = index.borrow::<rlua::Integer>() {
-                return Ok(*this + (*index as $cast));
+                return Ok(*this + <$cast as $crate::scalar::Scalar>::from_f64(*index as f64));
             }
             if let Ok(index) = index.borrow::<rlua::Number>() {
-                return Ok(*this + *index as $cast);
+                return Ok(*this + <$cast as $crate::scalar::Scalar>::from_f64(*index));
             }
             Ok(*this)
         });
@@ -53,10 +54,10 @@ macro_rules! lua_vector_methods {
         });
         $methods.add_meta_method(rlua::MetaMethod::Mul, |_, this, index: rlua::AnyUserData| {
             if let Ok(index) = index.borrow::<rlua::Integer>() {
-                return Ok(*this * (*index as $cast));
+                return Ok(*this * <$cast as $crate::scalar::Scalar>::from_f64(*index as f64));
             }
             if let Ok(index) = index.borrow::<rlua::Number>() {
-                return Ok(*this * *index as $cast);
+                return Ok(*this * <$cast as $crate::scalar::Scalar>::from_f64(*index));
             }
             Ok(*this)
         });
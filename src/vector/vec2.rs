@@ -1,9 +1,32 @@
+use crate::angle::Angle;
+use crate::fixed::Q16_16;
+use crate::scalar::Scalar;
+use crate::vector::vec3::Vec3;
 use crate::vector::Vector;
 use crate::{lua_vector, lua_vector_methods};
 
 pub type Vec2<S> = Vector<2, S>;
 pub type Vec2F = Vec2<f32>;
 pub type Vec2D = Vec2<f64>;
+///Deterministic fixed-point vector, see `Q16_16`.
+pub type Vec2X = Vec2<Q16_16>;
+
+impl<S: Scalar> Vec2<S> {
+    ///Homogeneous coordinates of the point, `w = 1`.
+    pub fn to_point(self) -> Vec3<S> {
+        Vec3::from_array([self[0], self[1], S::ONE])
+    }
+
+    ///Homogeneous coordinates of the direction, `w = 0`, so translation does not affect it.
+    pub fn to_direction(self) -> Vec3<S> {
+        self.up()
+    }
+
+    ///Counter-clockwise angle from the positive x-axis, in `(-π, π]`.
+    pub fn angle(self) -> Angle<S> {
+        Angle::atan2(self[1], self[0])
+    }
+}
 
 lua_vector!(Vec2F[f32] {
     Args = (Option<f32>, Option<f32>),
@@ -25,7 +48,9 @@ lua_vector!(Vec2F[f32] {
         }
     ]
     methods = (methods) {
-
+        fn polar_angle(this, _a: ()) {
+            Ok(this.angle())
+        }
     }
 });
 
@@ -52,5 +77,34 @@ lua_vector!(Vec2D[f64] {
         fn up(this, _a: ()) {
             Ok(this.up())
         }
+        fn polar_angle(this, _a: ()) {
+            Ok(this.angle())
+        }
     }
-});
\ No newline at end of file
+});
+
+lua_vector!(Vec2X[Q16_16] {
+    Args = (Option<Q16_16>, Option<Q16_16>),
+    CONSTRUCTOR_NAME = "vec2x",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args| {
+            Ok(Self::from_array([
+                args.0.unwrap_or(Q16_16::ZERO),
+                args.1.unwrap_or(Q16_16::ZERO),
+            ]))
+        })
+    },
+    associated_functions = (lua) [
+        fn vec2x_zero(_args: ()) {
+            Ok(Self::ZERO)
+        }
+        fn vec2x_one(_args: ()) {
+            Ok(Self::ONE)
+        }
+    ]
+    methods = (methods) {
+        fn polar_angle(this, _a: ()) {
+            Ok(this.angle())
+        }
+    }
+});
@@ -1,10 +1,14 @@
+use crate::fixed::Q16_16;
 use crate::scalar::Scalar;
 use crate::vector::Vector;
+use crate::vector::vec4::Vec4;
 use crate::{lua_vector, property};
 
 pub type Vec3<S> = Vector<3, S>;
 pub type Vec3F = Vec3<f32>;
 pub type Vec3D = Vec3<f64>;
+///Deterministic fixed-point vector, see `Q16_16`.
+pub type Vec3X = Vec3<Q16_16>;
 
 impl<S: Scalar> Vec3<S> {
     pub const RIGHT: Self = Self::from_array([S::ONE, S::ZERO, S::ZERO]);
@@ -23,6 +27,16 @@ impl<S: Scalar> Vec3<S> {
         self(x_y_z) -> (S, S, S) { (self.0[0], self.0[1], self.0[2]) }
     );
 
+    ///Homogeneous coordinates of the point, `w = 1`.
+    pub fn to_point(self) -> Vec4<S> {
+        Vec4::from_array([self[0], self[1], self[2], S::ONE])
+    }
+
+    ///Homogeneous coordinates of the direction, `w = 0`, so translation does not affect it.
+    pub fn to_direction(self) -> Vec4<S> {
+        self.up()
+    }
+
     pub fn cross(&self, v: &Self) -> Self {
         let (ax, ay, az) = self.x_y_z();
         let (bx, by, bz) = v.x_y_z();
@@ -90,3 +104,30 @@ lua_vector!(Vec3D[f64] {
         }
     }
 });
+
+lua_vector!(Vec3X[Q16_16] {
+    Args = (Option<Q16_16>, Option<Q16_16>, Option<Q16_16>),
+    CONSTRUCTOR_NAME = "vec3x",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args| {
+            Ok(Self::from_array([
+                args.0.unwrap_or(Q16_16::ZERO),
+                args.1.unwrap_or(Q16_16::ZERO),
+                args.2.unwrap_or(Q16_16::ZERO),
+            ]))
+        })
+    },
+    associated_functions = (lua) [
+        fn vec3x_zero(_args: ()) {
+            Ok(Self::ZERO)
+        }
+        fn vec3x_one(_args: ()) {
+            Ok(Self::ONE)
+        }
+    ]
+    methods = (methods) {
+        fn cross_product(this, b: Self) {
+            Ok(this.cross(&b))
+        }
+    }
+});
@@ -1,9 +1,25 @@
 use crate::{lua_vector, lua_vector_methods};
+use crate::fixed::Q16_16;
+use crate::scalar::Scalar;
+use crate::vector::vec3::Vec3;
 use crate::vector::Vector;
 
 pub type Vec4<S> = Vector<4, S>;
 pub type Vec4F = Vec4<f32>;
 pub type Vec4D = Vec4<f64>;
+///Deterministic fixed-point vector, see `Q16_16`.
+pub type Vec4X = Vec4<Q16_16>;
+
+impl<S: Scalar> Vec4<S> {
+    ///Divides by `w` and drops it. Directions (`w = 0`) only drop `w`.
+    pub fn perspective_divide(self) -> Vec3<S> {
+        let xyz = self.down();
+        match self[3] == S::ZERO {
+            true => xyz,
+            false => xyz * (S::ONE / self[3]),
+        }
+    }
+}
 
 lua_vector!(Vec4F[f32] {
     Args = (Option<f32>, Option<f32>, Option<f32>, Option<f32>),
@@ -29,4 +45,55 @@ lua_vector!(Vec4F[f32] {
     methods = (methods) {
 
     }
-});
\ No newline at end of file
+});
+
+lua_vector!(Vec4D[f64] {
+    Args = (Option<f64>, Option<f64>, Option<f64>, Option<f64>),
+    CONSTRUCTOR_NAME = "vec4d",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args| {
+            Ok(Self::from_array([
+                args.0.unwrap_or(0f64),
+                args.1.unwrap_or(0f64),
+                args.2.unwrap_or(0f64),
+                args.3.unwrap_or(0f64),
+            ]))
+        })
+    },
+    associated_functions = (lua) [
+        fn vec4d_zero(_args: ()) {
+            Ok(Self::ZERO)
+        }
+        fn vec4d_one(_args: ()) {
+            Ok(Self::ONE)
+        }
+    ]
+    methods = (methods) {
+
+    }
+});
+
+lua_vector!(Vec4X[Q16_16] {
+    Args = (Option<Q16_16>, Option<Q16_16>, Option<Q16_16>, Option<Q16_16>),
+    CONSTRUCTOR_NAME = "vec4x",
+    create_constructor = (lua) {
+        lua.create_function(|_, args: Self::Args| {
+            Ok(Self::from_array([
+                args.0.unwrap_or(Q16_16::ZERO),
+                args.1.unwrap_or(Q16_16::ZERO),
+                args.2.unwrap_or(Q16_16::ZERO),
+                args.3.unwrap_or(Q16_16::ZERO),
+            ]))
+        })
+    },
+    associated_functions = (lua) [
+        fn vec4x_zero(_args: ()) {
+            Ok(Self::ZERO)
+        }
+        fn vec4x_one(_args: ()) {
+            Ok(Self::ONE)
+        }
+    ]
+    methods = (methods) {
+    }
+});